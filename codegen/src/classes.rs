@@ -0,0 +1,89 @@
+//! The pseudo grammar for the classes! macro.
+//!
+//! CLASSES -> CLASS , CLASSES | CLASS | EPS
+//!
+//! CLASS -> EXPR => EXPR | EXPR
+//!
+//! N.B. EPS is Epsilon and EXPR is a Rust construct.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream, Result as ParseResult},
+    punctuated::Punctuated,
+    Expr, Token,
+};
+
+pub struct Classes {
+    pub classes: Punctuated<Class, Token![,]>,
+}
+
+impl Parse for Classes {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        Ok(Classes {
+            classes: Punctuated::parse_terminated(input)?,
+        })
+    }
+}
+
+impl Classes {
+    pub fn expand(&self) -> TokenStream {
+        let classes: Vec<_> = self.classes.iter().map(Class::expand).collect();
+        quote! {
+            {
+                let mut classes = ruukh::vdom::velement::ClassList::new();
+                #(#classes)*
+                classes
+            }
+        }
+    }
+}
+
+/// A class which is only added if the condition after `=>` holds.
+pub struct Class {
+    pub class: Expr,
+    pub condition: Option<Expr>,
+}
+
+impl Parse for Class {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        let class = input.parse()?;
+        let condition = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Class { class, condition })
+    }
+}
+
+impl Class {
+    fn expand(&self) -> TokenStream {
+        let class = &self.class;
+        match self.condition {
+            Some(ref condition) => quote! {
+                if #condition {
+                    classes.add(#class);
+                }
+            },
+            None => quote! {
+                classes.add(#class);
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_classes() {
+        let parsed: Classes =
+            syn::parse_str(r#""btn", "active" => self.active, self.size(),"#).unwrap();
+        assert_eq!(parsed.classes.len(), 3);
+        assert!(parsed.classes[0].condition.is_none());
+        assert!(parsed.classes[1].condition.is_some());
+    }
+}
@@ -1,18 +1,103 @@
-use self::{events::EventsMeta, fields::ComponentField, props::PropsMeta, state::StateMeta};
-use crate::suffix::STATUS_SUFFIX;
+pub use self::function::FnComponent;
+use self::{
+    computed::{changed_flag, ComputedMeta},
+    events::EventsMeta,
+    fields::ComponentField,
+    props::PropsMeta,
+    state::StateMeta,
+};
+use crate::{suffix::STATUS_SUFFIX, watch::watcher_ident};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use std::mem;
 use syn::{
-    parse::{Error, Result as ParseResult},
-    Attribute, Ident, ItemStruct, Visibility,
+    custom_keyword,
+    parse::{Error, Parse, ParseStream, Result as ParseResult},
+    spanned::Spanned,
+    Attribute, Expr, Generics, Ident, ItemStruct, Token, Visibility,
 };
 
+mod computed;
 mod events;
 mod fields;
+mod function;
+mod generics;
 mod props;
 mod state;
 
+custom_keyword!(history);
+custom_keyword!(inspect);
+
+/// The arguments passed with `#[component]` attribute.
+///
+/// Can be `#[component]` or a comma-separated list of `history`,
+/// `history = limit` & `inspect`, like `#[component(history, inspect)]`.
+#[derive(Default)]
+pub struct ComponentArgs {
+    /// The undo steps to keep, if the history of the state is recorded.
+    history: Option<Expr>,
+    /// Whether the component is inspected by the developer tools.
+    inspect: bool,
+}
+
+impl Parse for ComponentArgs {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        let mut args = ComponentArgs::default();
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(history) {
+                let keyword = input.parse::<history>()?;
+                if args.history.is_some() {
+                    return Err(Error::new(keyword.span, "`history` is declared twice."));
+                }
+                let limit = if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    input.parse()?
+                } else {
+                    syn::parse_quote!(ruukh::history::DEFAULT_LIMIT)
+                };
+                args.history = Some(limit);
+            } else if lookahead.peek(inspect) {
+                let keyword = input.parse::<inspect>()?;
+                if args.inspect {
+                    return Err(Error::new(keyword.span, "`inspect` is declared twice."));
+                }
+                args.inspect = true;
+            } else {
+                return Err(lookahead.error());
+            }
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// The item on which `#[component]` is placed, either a struct or a function.
+pub enum ComponentItem {
+    Struct(ItemStruct),
+    Fn(FnComponent),
+}
+
+impl Parse for ComponentItem {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        let fork = input.fork();
+        fork.call(Attribute::parse_outer)?;
+        fork.parse::<Visibility>()?;
+
+        if fork.peek(Token![struct]) {
+            Ok(ComponentItem::Struct(input.parse()?))
+        } else if fork.peek(Token![fn]) {
+            Ok(ComponentItem::Fn(input.parse()?))
+        } else {
+            Err(fork.error("Only structs & functions are allowed to be Component."))
+        }
+    }
+}
+
 /// All the necessary metadata taken from the struct declaration to construct
 /// a working Component.
 pub struct ComponentMeta {
@@ -22,36 +107,84 @@ pub struct ComponentMeta {
     vis: Visibility,
     /// Ident of component.
     ident: Ident,
+    /// Generics of component, bounded by `'static`.
+    generics: Generics,
     /// Props metadata if any prop fields.
     props_meta: PropsMeta,
     /// State metadata if any state fields.
     state_meta: StateMeta,
     /// Events metadata if any events declaration.
     events_meta: EventsMeta,
+    /// Computed fields metadata if any.
+    computed_meta: ComputedMeta,
+    /// Whether the component is exported as a Custom Element.
+    is_custom_element: bool,
+    /// The undo steps to keep, if the history of the state is recorded.
+    history: Option<Expr>,
+    /// Whether the component is inspected by the developer tools.
+    inspect: bool,
+}
+
+/// The ident of the old value of a field during an update.
+fn old_value(ident: &Ident) -> Ident {
+    Ident::new(&format!("__{}_old__", ident), Span::call_site())
+}
+
+/// Declares the empty watchers of the `fields`. The watchers declared with
+/// `#[watch(field)]` take precedence over these, being inherent methods of the
+/// component.
+fn unwatched_trait(fields: &[&Ident]) -> TokenStream {
+    let watchers = fields.iter().map(|field| watcher_ident(field));
+    quote! {
+        #[allow(dead_code)]
+        trait Unwatched {
+            #(
+                fn #watchers<T: ?Sized>(&self, _: &T, _: &T) {}
+            )*
+        }
+        impl<C> Unwatched for C {}
+    }
 }
 
 impl ComponentMeta {
-    pub fn parse(mut item: ItemStruct) -> ParseResult<ComponentMeta> {
+    pub fn parse(args: ComponentArgs, mut item: ItemStruct) -> ParseResult<ComponentMeta> {
         // Remove `#[component]` attribute.
         Self::filter_out_component_attribute(&mut item);
+        let is_custom_element = Self::filter_out_custom_element_attribute(&mut item)?;
 
-        if item.generics != Default::default() {
+        generics::normalize(&mut item.generics)?;
+
+        let computed_meta = ComputedMeta::parse(&mut item)?;
+        let (props_meta, state_meta) = ComponentField::parse_into_prop_and_state_meta(&mut item)?;
+        let events_meta = EventsMeta::parse(&mut item)?;
+
+        let fields: Vec<_> = props_meta
+            .fields
+            .iter()
+            .chain(state_meta.fields.iter())
+            .map(|f| &f.ident)
+            .collect();
+        computed_meta.verify_deps(&fields)?;
+
+        if args.history.is_some() && state_meta.fields.is_empty() {
             return Err(Error::new(
                 item.ident.span(),
-                "Generic parameters not allowed on a component.",
+                "`history` requires the component to have state fields.",
             ));
         }
 
-        let (props_meta, state_meta) = ComponentField::parse_into_prop_and_state_meta(&mut item)?;
-        let events_meta = EventsMeta::parse(&mut item)?;
-
         Ok(ComponentMeta {
             attrs: item.attrs,
             vis: item.vis,
             ident: item.ident,
+            generics: item.generics,
             props_meta,
             state_meta,
             events_meta,
+            computed_meta,
+            is_custom_element,
+            history: args.history,
+            inspect: args.inspect,
         })
     }
 
@@ -66,23 +199,50 @@ impl ComponentMeta {
         item.attrs = attrs;
     }
 
+    /// Removes `#[custom_element]` attribute and returns whether it existed.
+    fn filter_out_custom_element_attribute(item: &mut ItemStruct) -> ParseResult<bool> {
+        let mut attrs = vec![];
+        mem::swap(&mut attrs, &mut item.attrs);
+        let custom_element = Ident::new("custom_element", Span::call_site()).into();
+        let (custom_element_attrs, rest): (Vec<_>, Vec<_>) = attrs
+            .into_iter()
+            .partition(|attr| attr.path == custom_element);
+        item.attrs = rest;
+
+        if let Some(attr) = custom_element_attrs.iter().find(|attr| !attr.tts.is_empty()) {
+            return Err(Error::new(
+                attr.span(),
+                "`#[custom_element]` does not support attribute arguments.",
+            ));
+        }
+        Ok(!custom_element_attrs.is_empty())
+    }
+
     pub fn expand(&self) -> TokenStream {
         let component_struct = self.create_component_struct();
         let component_impl = self.impl_component_trait_on_component_struct();
         let set_state_impl = self.impl_set_state_trait_on_component_struct();
         let state_setter_impl = self.impl_state_setter_trait_on_component_struct();
         let state_struct = self.state_meta.create_state_struct();
+        let (state_clone_impl, undo_impl) = if self.history.is_some() {
+            (Some(self.state_meta.impl_clone_trait()), Some(self.impl_undo_trait()))
+        } else {
+            (None, None)
+        };
         let props_struct = self.props_meta.create_props_struct_and_macro();
         let events_structs = self
             .events_meta
             .create_events_and_event_props_struct_and_macro();
         let status_wrapper_struct = self.create_status_wrapper_struct();
+        let custom_element_impls = self.impl_custom_element_traits();
 
         quote! {
             #component_struct
 
             #state_struct
 
+            #state_clone_impl
+
             #props_struct
 
             #events_structs
@@ -94,6 +254,33 @@ impl ComponentMeta {
             #status_wrapper_struct
 
             #state_setter_impl
+
+            #undo_impl
+
+            #custom_element_impls
+        }
+    }
+
+    fn impl_undo_trait(&self) -> TokenStream {
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        quote! {
+            impl #impl_generics ruukh::history::Undo for #ident #ty_generics #where_clause {}
+        }
+    }
+
+    fn impl_custom_element_traits(&self) -> Option<TokenStream> {
+        if self.is_custom_element {
+            let from_attributes_impl = self.props_meta.impl_from_attributes_trait();
+            let dispatch_events_impl = self.events_meta.impl_dispatch_events_trait();
+
+            Some(quote! {
+                #from_attributes_impl
+
+                #dispatch_events_impl
+            })
+        } else {
+            None
         }
     }
 
@@ -101,26 +288,30 @@ impl ComponentMeta {
         let attrs = &self.attrs;
         let ident = &self.ident;
         let vis = &self.vis;
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
 
         if self.props_meta.fields.is_empty()
             && self.state_meta.fields.is_empty()
+            && self.computed_meta.fields.is_empty()
             && self.events_meta.events.is_empty()
         {
             quote! {
                 #(#attrs)*
-                #vis struct #ident;
+                #vis struct #ident #impl_generics #where_clause;
             }
         } else {
             let state_fields = self.state_meta.to_struct_fields();
             let props_fields = self.props_meta.to_struct_fields();
+            let computed_fields = self.computed_meta.to_struct_fields();
             let status_field = self.create_status_field();
             let events_field = self.create_events_field();
 
             quote! {
                 #(#attrs)*
-                #vis struct #ident {
+                #vis struct #ident #impl_generics #where_clause {
                     #(#state_fields ,)*
                     #(#props_fields ,)*
+                    #(#computed_fields ,)*
                     #status_field
                     #events_field
                 }
@@ -132,7 +323,7 @@ impl ComponentMeta {
         if self.props_meta.fields.is_empty() && self.state_meta.fields.is_empty() {
             None
         } else {
-            let status_ty = self.get_status_type();
+            let status_ty = self.get_status_type_with_generics();
             Some(quote! {
                 __status__: #status_ty,
             })
@@ -143,9 +334,9 @@ impl ComponentMeta {
         if self.events_meta.events.is_empty() {
             None
         } else {
-            let ident = &self.events_meta.ident;
+            let events_ty = self.events_meta.to_type();
             Some(quote! {
-                __events__: #ident,
+                __events__: #events_ty,
             })
         }
     }
@@ -154,8 +345,7 @@ impl ComponentMeta {
         if self.props_meta.fields.is_empty() {
             quote!(())
         } else {
-            let ident = &self.props_meta.ident;
-            quote!(#ident)
+            self.props_meta.to_type()
         }
     }
 
@@ -163,8 +353,7 @@ impl ComponentMeta {
         if self.state_meta.fields.is_empty() {
             quote!(())
         } else {
-            let ident = &self.state_meta.ident;
-            quote!(#ident)
+            self.state_meta.to_type()
         }
     }
 
@@ -172,8 +361,7 @@ impl ComponentMeta {
         if self.events_meta.events.is_empty() {
             quote!(())
         } else {
-            let ident = &self.events_meta.ident;
-            quote!(#ident)
+            self.events_meta.to_type()
         }
     }
 
@@ -184,17 +372,43 @@ impl ComponentMeta {
         )
     }
 
+    /// The generics of the status wrapper, which only depends on the state.
+    fn get_status_generics(&self) -> Generics {
+        if self.state_meta.fields.is_empty() {
+            Generics::default()
+        } else {
+            self.generics.clone()
+        }
+    }
+
+    fn get_status_type_with_generics(&self) -> TokenStream {
+        let ident = self.get_status_type();
+        let generics = self.get_status_generics();
+        let (_, ty_generics, _) = generics.split_for_impl();
+        quote!(#ident #ty_generics)
+    }
+
     fn create_status_wrapper_struct(&self) -> Option<TokenStream> {
         if self.props_meta.fields.is_empty() && self.state_meta.fields.is_empty() {
             None
         } else {
             let ident = self.get_status_type();
+            let generics = self.get_status_generics();
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
             let state_ty = self.get_state_type();
             let status_set_state = self.impl_set_state_trait_for_status_wrapper();
 
+            // Not derived, as it would bound the type parameters with `Clone`.
             Some(quote! {
-                #[derive(Clone)]
-                struct #ident(std::rc::Rc<std::cell::RefCell<ruukh::component::Status<#state_ty>>>);
+                struct #ident #impl_generics (
+                    std::rc::Rc<std::cell::RefCell<ruukh::component::Status<#state_ty>>>
+                ) #where_clause;
+
+                impl #impl_generics Clone for #ident #ty_generics #where_clause {
+                    fn clone(&self) -> Self {
+                        #ident(self.0.clone())
+                    }
+                }
 
                 #status_set_state
             })
@@ -206,14 +420,18 @@ impl ComponentMeta {
             None
         } else {
             let ident = self.get_status_type();
+            let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
             let state_ty = self.get_state_type();
+            let (snapshot, record) = self.expand_history_record();
             Some(quote! {
-                impl SetState for #ident {
+                impl #impl_generics SetState for #ident #ty_generics #where_clause {
                     type State = #state_ty;
 
                     fn set_state(&self, mut mutator: impl FnMut(&mut Self::State)) {
                         let mut status = self.0.borrow_mut();
+                        #snapshot
                         mutator(status.state_as_mut());
+                        #record
                         status.set_state_dirty(true);
                         status.do_react();
                     }
@@ -224,16 +442,19 @@ impl ComponentMeta {
 
     fn impl_component_trait_on_component_struct(&self) -> TokenStream {
         let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
         let props_type = &self.get_props_type();
         let state_type = &self.get_state_type();
         let events_type = &self.get_events_type();
         let init_body = self.impl_fn_init_body();
+        let initial_state_fn = self.state_meta.impl_fn_initial_state();
         let update_body = self.impl_fn_update_body();
         let refresh_state_body = self.impl_fn_refresh_state_body();
         let status_body = self.impl_fn_status_body();
+        let devtools_fns = self.impl_devtools_fns();
 
         quote! {
-            impl Component for #ident {
+            impl #impl_generics Component for #ident #ty_generics #where_clause {
                 type Props = #props_type;
                 type State = #state_type;
                 type Events = #events_type;
@@ -246,6 +467,8 @@ impl ComponentMeta {
                     #init_body
                 }
 
+                #initial_state_fn
+
                 fn update(
                     &mut self,
                     mut __props__: Self::Props,
@@ -265,21 +488,96 @@ impl ComponentMeta {
                 {
                     #status_body
                 }
+
+                #devtools_fns
             }
         }
     }
 
+    /// Reports the props & state to the developer tools and allows to edit the
+    /// state, when declared with `#[component(inspect)]` & the `devtools`
+    /// feature is enabled.
+    fn impl_devtools_fns(&self) -> Option<TokenStream> {
+        if !self.inspect || !cfg!(feature = "devtools") {
+            return None;
+        }
+
+        let prop_inserts = self.props_meta.expand_fields_with(|f| {
+            let ident = &f.ident;
+            let name = ident.to_string();
+            quote! {
+                inspection.props.insert(
+                    #name.to_string(),
+                    ruukh::devtools::debug_value(&self.#ident),
+                );
+            }
+        });
+        let state_inserts = self.state_meta.expand_fields_with(|f| {
+            let ident = &f.ident;
+            let name = ident.to_string();
+            quote! {
+                inspection.state.insert(
+                    #name.to_string(),
+                    ruukh::devtools::json_value(&self.#ident),
+                );
+            }
+        });
+        let inspect_fn = quote! {
+            fn inspect(&self) -> Option<ruukh::devtools::Inspection> {
+                #[allow(unused_mut)]
+                let mut inspection = ruukh::devtools::Inspection::default();
+                #(#prop_inserts)*
+                #(#state_inserts)*
+                Some(inspection)
+            }
+        };
+
+        let edit_state_fn = if self.state_meta.fields.is_empty() {
+            None
+        } else {
+            let edits = self.state_meta.expand_fields_with(|f| {
+                let ident = &f.ident;
+                let name = ident.to_string();
+                quote! {
+                    #name => status.state_as_mut().#ident = ruukh::devtools::from_json(value)?,
+                }
+            });
+            let (snapshot, record) = self.expand_history_record();
+
+            Some(quote! {
+                fn edit_state(&self, field: &str, value: &str) -> Result<(), String> {
+                    let mut status = self.__status__.0.borrow_mut();
+                    #snapshot
+                    match field {
+                        #(#edits)*
+                        _ => return Err(format!("There is no state field `{}`.", field)),
+                    }
+                    #record
+                    status.set_state_dirty(true);
+                    status.do_react();
+                    Ok(())
+                }
+            })
+        };
+
+        Some(quote! {
+            #inspect_fn
+            #edit_state_fn
+        })
+    }
+
     fn impl_set_state_trait_on_component_struct(&self) -> Option<TokenStream> {
         if self.state_meta.fields.is_empty() {
             None
         } else {
             let ident = &self.ident;
-            let state_ident = &self.state_meta.ident;
+            let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+            let state_ty = self.state_meta.to_type();
             let set_state_body = self.impl_fn_set_state_body();
 
             Some(quote! {
-                impl SetState for #ident {
-                    type State = #state_ident;
+                impl #impl_generics SetState for #ident #ty_generics #where_clause {
+                    type State = #state_ty;
 
                     fn set_state(&self, mut mutator: impl FnMut(&mut Self::State)) {
                         #set_state_body
@@ -294,10 +592,11 @@ impl ComponentMeta {
             None
         } else {
             let ident = &self.ident;
-            let status_ty = self.get_status_type();
+            let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+            let status_ty = self.get_status_type_with_generics();
 
             Some(quote! {
-                impl StateSetter for #ident {
+                impl #impl_generics StateSetter for #ident #ty_generics #where_clause {
                     type Setter = #status_ty;
 
                     fn state_setter(&self) -> Self::Setter {
@@ -312,19 +611,22 @@ impl ComponentMeta {
         if self.state_meta.fields.is_empty() {
             None
         } else {
-            let idents = &self.state_meta.to_field_idents();
-            let idents2 = idents;
+            let changed_checks = self
+                .state_meta
+                .expand_fields_with(|f| f.to_changed_check(&quote!(self), &quote!(state)));
+
+            let (snapshot, record) = self.expand_history_record();
 
             Some(quote! {
                 let mut status = self.__status__.0.borrow_mut();
+                #snapshot
                 mutator(status.state_as_mut());
                 let changed = {
                     let state = status.state_as_ref();
-                    #(
-                        self.#idents != state.#idents2
-                    ) ||*
+                    #(#changed_checks) ||*
                 };
                 if changed {
+                    #record
                     status.set_state_dirty(true);
                     status.do_react();
                 }
@@ -332,7 +634,63 @@ impl ComponentMeta {
         }
     }
 
+    /// Takes the snapshot of the state in `status` before it is mutated &
+    /// records it after if the state changed, if the history is recorded.
+    fn expand_history_record(&self) -> (Option<TokenStream>, Option<TokenStream>) {
+        if self.history.is_none() {
+            return (None, None);
+        }
+        let changed_checks = self
+            .state_meta
+            .expand_fields_with(|f| f.to_changed_check(&quote!(state), &quote!(__before__)));
+        (
+            Some(quote! {
+                let __before__ = status.state_as_ref().clone();
+            }),
+            Some(quote! {
+                let __changed__ = {
+                    let state = status.state_as_ref();
+                    #(#changed_checks) ||*
+                };
+                if __changed__ {
+                    status.record_history(__before__);
+                }
+            }),
+        )
+    }
+
     fn impl_fn_init_body(&self) -> TokenStream {
+        let persisted_loads: Vec<_> = self
+            .state_meta
+            .fields
+            .iter()
+            .filter_map(|f| f.to_persisted_load(&quote!(state)))
+            .collect();
+        let enable_history = self.history.as_ref().map(|limit| {
+            quote! {
+                __status__.enable_history(#limit);
+            }
+        });
+        let persisted_load = if persisted_loads.is_empty() {
+            None
+        } else {
+            Some(quote! {
+                {
+                    let state = __status__.state_as_mut();
+                    #(#persisted_loads)*
+                }
+            })
+        };
+        let status_setup = if persisted_load.is_none() && enable_history.is_none() {
+            None
+        } else {
+            Some(quote! {
+                let mut __status__ = __status__;
+                #persisted_load
+                #enable_history
+            })
+        };
+
         let state_clone = if self.state_meta.fields.is_empty() {
             None
         } else {
@@ -379,16 +737,39 @@ impl ComponentMeta {
         let state_field_idents = &self.state_meta.to_field_idents();
         let props_field_idents = &self.props_meta.to_field_idents();
         let props_field_idents2 = props_field_idents;
+        let computed_defaults = self.computed_meta.to_field_assignments_as_default();
 
-        quote! {
-            #state_clone
-
+        let component = quote! {
             #ident {
                 #(#props_field_idents: __props__.#props_field_idents2 ,)*
                 #(#state_field_idents ,)*
+                #(#computed_defaults ,)*
                 #event_assignment
                 #status_assignment
             }
+        };
+
+        if self.computed_meta.fields.is_empty() {
+            quote! {
+                #status_setup
+
+                #state_clone
+
+                #component
+            }
+        } else {
+            // The computed fields are computed once the component is created.
+            let component_ident = Ident::new("__component__", Span::call_site());
+            let initial_compute = self.computed_meta.expand_initial_compute(&component_ident);
+            quote! {
+                #status_setup
+
+                #state_clone
+
+                let mut #component_ident = #component;
+                #(#initial_compute)*
+                #component_ident
+            }
         }
     }
 
@@ -408,25 +789,51 @@ impl ComponentMeta {
                 false
             }
         } else {
-            let idents = &self.state_meta.to_field_idents();
-            let idents2 = idents;
-            let idents3 = idents;
-            let idents4 = idents;
+            let state_idents: Vec<_> = self.state_meta.fields.iter().map(|f| &f.ident).collect();
+            let flags: &Vec<_> = &state_idents.iter().map(|i| changed_flag(i)).collect();
+            let flags2 = flags;
+            let commits = self.state_meta.expand_fields_with(|f| {
+                let ident = &f.ident;
+                let flag = changed_flag(ident);
+                let changed_check = f.to_changed_check(&quote!(self), &quote!(state));
+                // The old value of the field is kept for its watcher.
+                let old = old_value(ident);
+                let save = f.to_persisted_save();
+                quote! {
+                    if #changed_check {
+                        #old = Some(std::mem::replace(&mut self.#ident, state.#ident.clone()));
+                        #save
+                        #flag = true;
+                    }
+                }
+            });
+            let olds: &Vec<_> = &state_idents.iter().map(|i| old_value(i)).collect();
+            let olds2 = olds;
+            let watcher_calls = self
+                .state_meta
+                .fields
+                .iter()
+                .map(|f| f.to_watcher_call(&quote!(__old__)));
+            let unwatched = unwatched_trait(&state_idents);
+            let recompute = self.computed_meta.expand_recompute(&state_idents);
 
             quote! {
-                let status = self.__status__.0.borrow();
-                let state = status.state_as_ref();
-                let mut changed = false;
-                #(
-                    if self.#idents != state.#idents2 {
-                        self.#idents3 = state.#idents4.clone();
+                #unwatched
 
-                        if !changed {
-                            changed = true;
-                        }
+                #(let mut #flags = false;)*
+                #(let mut #olds = None;)*
+                {
+                    let status = self.__status__.0.borrow();
+                    let state = status.state_as_ref();
+                    #(#commits)*
+                }
+                #(#recompute)*
+                #(
+                    if let Some(ref __old__) = #olds2 {
+                        #watcher_calls
                     }
                 )*
-                changed
+                #(#flags2)||*
             }
         }
     }
@@ -450,8 +857,28 @@ impl ComponentMeta {
         } else {
             let idents = &self.props_meta.to_field_idents();
             let idents2 = idents;
+            let changed_checks = self
+                .props_meta
+                .expand_fields_with(|f| f.to_changed_check(&quote!(self), &quote!(__props__)));
+            let props_idents: Vec<_> = self.props_meta.fields.iter().map(|f| &f.ident).collect();
+            let flags: &Vec<_> = &props_idents.iter().map(|i| changed_flag(i)).collect();
+            let flags2 = flags;
+            let recompute = self.computed_meta.expand_recompute(&props_idents);
+            let watchers = self.props_meta.fields.iter().map(|f| {
+                let flag = changed_flag(&f.ident);
+                let ident = &f.ident;
+                let call = f.to_watcher_call(&quote!(&__props__.#ident));
+                quote! {
+                    if #flag {
+                        #call
+                    }
+                }
+            });
+            let unwatched = unwatched_trait(&props_idents);
 
             quote! {
+                #unwatched
+
                 #events_assignment
 
                 use std::mem;
@@ -459,7 +886,11 @@ impl ComponentMeta {
                     mem::swap(&mut self.#idents, &mut __props__.#idents2);
                 )*
 
-                if #(self.#idents != __props__.#idents2) || * {
+                #(let #flags = #changed_checks;)*
+                #(#recompute)*
+                #(#watchers)*
+
+                if #(#flags2) || * {
                     self.__status__.0.borrow_mut().set_props_dirty(true);
                     Some(__props__)
                 } else {
@@ -0,0 +1,282 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use std::mem;
+use syn::{
+    bracketed, custom_keyword, parenthesized,
+    parse::{Error, Parse, ParseStream, Result as ParseResult},
+    punctuated::Punctuated,
+    Attribute, Field, Fields, Ident, ItemStruct, Path, Token, Type, Visibility,
+};
+
+/// Stores the list of computed fields, in the order of their declaration.
+pub struct ComputedMeta {
+    /// List of computed fields.
+    pub fields: Vec<ComputedField>,
+}
+
+impl ComputedMeta {
+    /// Takes out the fields marked with `#[computed]` from the component,
+    /// leaving behind the prop & state fields.
+    pub fn parse(component: &mut ItemStruct) -> ParseResult<ComputedMeta> {
+        let named = match component.fields {
+            Fields::Named(ref mut fields) => &mut fields.named,
+            _ => return Ok(ComputedMeta { fields: vec![] }),
+        };
+
+        let mut taken = Punctuated::new();
+        mem::swap(named, &mut taken);
+
+        let computed = Ident::new("computed", Span::call_site()).into();
+        let (computed_fields, rest): (Vec<_>, Vec<_>) = taken
+            .into_iter()
+            .partition(|field| field.attrs.iter().any(|attr| attr.path == computed));
+        named.extend(rest);
+
+        let fields: ParseResult<Vec<_>> = computed_fields
+            .into_iter()
+            .map(|field| ComputedField::parse(field, &computed))
+            .collect();
+        Ok(ComputedMeta { fields: fields? })
+    }
+
+    /// Verifies that the dependencies are either one of the `fields` or a
+    /// computed field declared before.
+    pub fn verify_deps(&self, fields: &[&Ident]) -> ParseResult<()> {
+        let mut known: Vec<&Ident> = fields.to_vec();
+        for field in self.fields.iter() {
+            if let Some(dep) = field.deps.iter().find(|dep| !known.contains(dep)) {
+                return Err(Error::new(
+                    dep.span(),
+                    format!(
+                        "Unknown dependency `{}`. Expected a prop, a state or a \
+                         computed field declared before.",
+                        dep
+                    ),
+                ));
+            }
+            known.push(&field.ident);
+        }
+        Ok(())
+    }
+
+    pub fn to_struct_fields(&self) -> Vec<TokenStream> {
+        self.fields
+            .iter()
+            .map(ComputedField::to_struct_field)
+            .collect()
+    }
+
+    pub fn to_field_assignments_as_default(&self) -> Vec<TokenStream> {
+        self.fields
+            .iter()
+            .map(|field| {
+                let ident = &field.ident;
+                quote! {
+                    #ident: Default::default()
+                }
+            }).collect()
+    }
+
+    /// Computes all the fields on the freshly created `component`.
+    pub fn expand_initial_compute(&self, component: &Ident) -> Vec<TokenStream> {
+        self.fields
+            .iter()
+            .map(|field| {
+                let ident = &field.ident;
+                let compute_fn = field.compute_fn();
+                quote! {
+                    #component.#ident = #component.#compute_fn();
+                }
+            }).collect()
+    }
+
+    /// Recomputes the fields which depend on any of the `changed` fields,
+    /// whose change flags are already declared by then. Change flags are also
+    /// declared for the recomputed fields, for other computed fields that
+    /// depend on them.
+    pub fn expand_recompute(&self, changed: &[&Ident]) -> Vec<TokenStream> {
+        let mut changed: Vec<&Ident> = changed.to_vec();
+        let mut recomputes = vec![];
+        for field in self.fields.iter() {
+            let dep_flags: Vec<_> = field
+                .deps
+                .iter()
+                .filter(|dep| changed.contains(dep))
+                .map(changed_flag)
+                .collect();
+            if dep_flags.is_empty() {
+                continue;
+            }
+
+            let ident = &field.ident;
+            let compute_fn = field.compute_fn();
+            let flag = changed_flag(ident);
+            recomputes.push(quote! {
+                let #flag = #(#dep_flags)||*;
+                if #flag {
+                    self.#ident = self.#compute_fn();
+                }
+            });
+            changed.push(ident);
+        }
+        recomputes
+    }
+}
+
+/// The ident of the flag which tells whether the field changed during an
+/// update.
+pub fn changed_flag(ident: &Ident) -> Ident {
+    Ident::new(&format!("__{}_changed__", ident), Span::call_site())
+}
+
+/// A field whose value is derived from the other fields of a component. It is
+/// computed by the `compute_<field>(&self)` method of the component.
+pub struct ComputedField {
+    pub attrs: Vec<Attribute>,
+    pub vis: Visibility,
+    pub ident: Ident,
+    pub ty: Type,
+    /// The fields on which its value depends.
+    pub deps: Vec<Ident>,
+}
+
+impl ComputedField {
+    fn parse(field: Field, computed: &Path) -> ParseResult<ComputedField> {
+        let ident = field.ident.unwrap();
+        let (mut computed_attrs, rest): (Vec<_>, Vec<_>) = field
+            .attrs
+            .into_iter()
+            .partition(|attr| &attr.path == computed);
+
+        if computed_attrs.len() > 1 {
+            return Err(Error::new(
+                ident.span(),
+                "Cannot have multiple `#[computed]` attributes.",
+            ));
+        }
+        let prop = Ident::new("prop", Span::call_site()).into();
+        let state = Ident::new("state", Span::call_site()).into();
+        if rest
+            .iter()
+            .any(|attr| attr.path == prop || attr.path == state)
+        {
+            return Err(Error::new(
+                ident.span(),
+                "A `#[computed]` field cannot be a `#[prop]` or `#[state]`.",
+            ));
+        }
+        let watch = Ident::new("watch", Span::call_site()).into();
+        if rest.iter().any(|attr| attr.path == watch) {
+            return Err(Error::new(
+                ident.span(),
+                "A `#[computed]` field cannot be watched.",
+            ));
+        }
+
+        let arg: ComputedArg = syn::parse2(computed_attrs.remove(0).tts)?;
+
+        Ok(ComputedField {
+            attrs: rest,
+            vis: field.vis,
+            ident,
+            ty: field.ty,
+            deps: arg.deps,
+        })
+    }
+
+    fn to_struct_field(&self) -> TokenStream {
+        let attrs = &self.attrs;
+        let vis = &self.vis;
+        let ident = &self.ident;
+        let ty = &self.ty;
+        quote! {
+            #(#attrs)*
+            #vis #ident: #ty
+        }
+    }
+
+    fn compute_fn(&self) -> Ident {
+        Ident::new(&format!("compute_{}", self.ident), self.ident.span())
+    }
+}
+
+custom_keyword!(deps);
+
+/// The argument passed with `#[computed]` attribute.
+///
+/// Can be `#[computed]` or `#[computed(deps = [field, ...])]`.
+struct ComputedArg {
+    deps: Vec<Ident>,
+}
+
+impl Parse for ComputedArg {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        if input.is_empty() {
+            return Ok(ComputedArg { deps: vec![] });
+        }
+
+        let content;
+        parenthesized!(content in input);
+        content.parse::<deps>()?;
+        content.parse::<Token![=]>()?;
+
+        let list;
+        bracketed!(list in content);
+        let deps = Punctuated::<Ident, Token![,]>::parse_terminated(&list)?;
+
+        if !content.is_empty() {
+            return Err(content.error("expected `)`."));
+        }
+        Ok(ComputedArg {
+            deps: deps.into_iter().collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_computed_arg() {
+        let arg: ComputedArg = syn::parse_str("(deps = [items, filter])").unwrap();
+        assert_eq!(arg.deps.len(), 2);
+        assert_eq!(arg.deps[0], "items");
+
+        let arg: ComputedArg = syn::parse_str("").unwrap();
+        assert!(arg.deps.is_empty());
+    }
+
+    #[test]
+    fn should_take_out_computed_fields() {
+        let mut item: ItemStruct = syn::parse_str(
+            "struct List {
+                items: Vec<i32>,
+                #[computed(deps = [items])]
+                total: i32,
+            }",
+        ).unwrap();
+        let meta = ComputedMeta::parse(&mut item).unwrap();
+
+        assert_eq!(meta.fields.len(), 1);
+        assert_eq!(meta.fields[0].ident, "total");
+        assert_eq!(item.fields.iter().count(), 1);
+    }
+
+    #[test]
+    fn should_not_verify_unknown_deps() {
+        let mut item: ItemStruct = syn::parse_str(
+            "struct List {
+                items: Vec<i32>,
+                #[computed(deps = [average])]
+                total: i32,
+                #[computed(deps = [items])]
+                average: i32,
+            }",
+        ).unwrap();
+        let meta = ComputedMeta::parse(&mut item).unwrap();
+        let items = Ident::new("items", Span::call_site());
+
+        assert!(meta.verify_deps(&[&items]).is_err());
+    }
+}
@@ -1,12 +1,14 @@
 use self::parser::{EventDeclaration, EventDeclarations};
+use super::generics;
 use crate::suffix::{EVENT_PROPS_SUFFIX, EVENT_SUFFIX};
+use heck::KebabCase;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use std::mem;
 use syn::{
     parse::{Error, Result as ParseResult},
     spanned::Spanned,
-    Attribute, FnArg, Ident, ItemStruct, Pat, ReturnType, Type, Visibility,
+    Attribute, FnArg, Generics, Ident, ItemStruct, Pat, ReturnType, Type, Visibility,
 };
 
 mod parser;
@@ -23,6 +25,8 @@ pub struct EventsMeta {
     pub component_ident: Ident,
     /// Visibility of the component.
     pub vis: Visibility,
+    /// Generics of the component.
+    pub generics: Generics,
     /// All the event declarations on the component.
     pub events: Vec<EventMeta>,
 }
@@ -53,6 +57,7 @@ impl EventsMeta {
             ),
             component_ident: component.ident.clone(),
             vis: component.vis.clone(),
+            generics: component.generics.clone(),
             events: event_metas,
         })
     }
@@ -69,6 +74,13 @@ impl EventsMeta {
         self.events.iter().map(map_fn).collect()
     }
 
+    /// The events type along with the generics of the component.
+    pub fn to_type(&self) -> TokenStream {
+        let ident = &self.ident;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        quote!(#ident #ty_generics)
+    }
+
     pub fn create_events_and_event_props_struct_and_macro(&self) -> TokenStream {
         if self.events.is_empty() {
             self.create_void_events_macro()
@@ -80,15 +92,18 @@ impl EventsMeta {
     fn _create_events_and_event_props_struct_and_macro(&self) -> TokenStream {
         let ident = &self.ident;
         let vis = &self.vis;
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
         let fields = self.expand_events_with(EventMeta::to_struct_field);
+        let phantom_field = generics::phantom_field(&self.generics);
         let event_names = &self.expand_events_with(EventMeta::to_event_name);
 
         let event_props_struct = self.create_event_props_struct(event_names);
         let events_macro = self.create_events_macro(event_names);
 
         quote! {
-            #vis struct #ident {
-                #(#fields),*
+            #vis struct #ident #impl_generics #where_clause {
+                #(#fields,)*
+                #phantom_field
             }
 
             #event_props_struct
@@ -103,6 +118,7 @@ impl EventsMeta {
         let event_props_ident = &self.event_props_ident;
         let component_ident = &self.component_ident;
         let macro_internal_ident = self.internal_macro_ident();
+        let phantom_assignment = generics::phantom_assignment(&self.generics);
 
         let mut next_event_names = event_names.to_owned();
         let first = next_event_names.remove(0);
@@ -151,7 +167,8 @@ impl EventsMeta {
                     tokens = [{ }]
                 ) => {
                     #event_props_ident {
-                        $($key: $val),*
+                        $($key: $val,)*
+                        #phantom_assignment
                     }
                 },
                 (
@@ -177,19 +194,27 @@ impl EventsMeta {
         let vis = &self.vis;
         let ident = &self.ident;
         let event_props_ident = &self.event_props_ident;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let rctx_generics = generics::with_render_context(&self.generics);
+        let (rctx_impl_generics, rctx_ty_generics, where_clause) = rctx_generics.split_for_impl();
         let gen_fields = self.expand_events_with(EventMeta::to_event_prop_field);
+        let phantom_field = generics::phantom_field(&self.generics);
+        let phantom_assignment = generics::phantom_assignment(&self.generics);
         let event_conversion =
             self.expand_events_with(EventMeta::impl_event_conversion_from_event_prop);
         let event_wrappers =
-            self.expand_events_with(|e| e.impl_event_wrapper(&self.component_ident));
+            self.expand_events_with(|e| e.impl_event_wrapper(&self.component_ident, &self.generics));
 
         quote! {
-            #vis struct #event_props_ident<RCTX: Render> {
-                #(#gen_fields),*
+            #vis struct #event_props_ident #rctx_impl_generics #where_clause {
+                #(#gen_fields,)*
+                #phantom_field
             }
 
-            impl<RCTX: Render> ruukh::component::FromEventProps<RCTX> for #ident {
-                type From = #event_props_ident<RCTX>;
+            impl #rctx_impl_generics ruukh::component::FromEventProps<RCTX>
+                for #ident #ty_generics #where_clause
+            {
+                type From = #event_props_ident #rctx_ty_generics;
 
                 fn from(
                     __rctx_events__: Self::From,
@@ -198,7 +223,8 @@ impl EventsMeta {
                     #(#event_conversion)*
 
                     #ident {
-                        #(#event_names),*
+                        #(#event_names,)*
+                        #phantom_assignment
                     }
                 }
             }
@@ -236,6 +262,34 @@ impl EventsMeta {
         }
     }
 
+    pub fn impl_dispatch_events_trait(&self) -> Option<TokenStream> {
+        if self.events.is_empty() {
+            None
+        } else {
+            let ident = &self.ident;
+            let event_props_ident = &self.event_props_ident;
+            let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+            let tys = self.generics.type_params().map(|param| &param.ident);
+            let event_dispatchers = self.expand_events_with(EventMeta::to_event_dispatcher);
+            let phantom_assignment = generics::phantom_assignment(&self.generics);
+
+            Some(quote! {
+                impl #impl_generics ruukh::custom_element::DispatchEvents
+                    for #ident #ty_generics #where_clause
+                {
+                    fn dispatch_to(
+                        __target__: &ruukh::reexports::EventTarget
+                    ) -> #event_props_ident<ruukh::component::RootParent, #(#tys),*> {
+                        #event_props_ident {
+                            #(#event_dispatchers,)*
+                            #phantom_assignment
+                        }
+                    }
+                }
+            })
+        }
+    }
+
     fn internal_macro_ident(&self) -> Ident {
         Ident::new(
             &format!("__new_{}_internal__", self.ident),
@@ -440,14 +494,15 @@ impl EventMeta {
         }
     }
 
-    fn impl_event_wrapper(&self, component_ident: &Ident) -> TokenStream {
+    fn impl_event_wrapper(&self, component_ident: &Ident, generics: &Generics) -> TokenStream {
         let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
         let arg_fields = self.to_arg_fields();
         let arg_idents = self.to_arg_idents();
         let ret_type = self.to_return_type();
 
         quote! {
-            impl #component_ident {
+            impl #impl_generics #component_ident #ty_generics #where_clause {
                 fn #ident (&self, #(#arg_fields),*) #ret_type {
                     (self.__events__.#ident)(#(#arg_idents),*)
                 }
@@ -455,6 +510,42 @@ impl EventMeta {
         }
     }
 
+    /// An event prop which dispatches a `CustomEvent` on the `__target__`.
+    fn to_event_dispatcher(&self) -> TokenStream {
+        let ident = &self.ident;
+        let event_type = self.ident.to_string().to_kebab_case();
+        let arg_fields = self.to_arg_fields();
+        let arg_idents = self.to_arg_idents();
+        let ret_value = match self.return_type {
+            ReturnType::Default => None,
+            ReturnType::Type(..) => Some(quote!(Default::default())),
+        };
+
+        let dispatcher = quote! {
+            {
+                let __target__ = __target__.clone();
+                Box::new(move |_: &ruukh::component::RootParent, #(#arg_fields),*| {
+                    ruukh::custom_element::dispatch_event(
+                        &__target__,
+                        #event_type,
+                        vec![#(ruukh::reexports::JsValue::from(#arg_idents)),*],
+                    );
+                    #ret_value
+                })
+            }
+        };
+
+        if self.is_optional {
+            quote! {
+                #ident: Some(#dispatcher)
+            }
+        } else {
+            quote! {
+                #ident: #dispatcher
+            }
+        }
+    }
+
     fn to_event_assignment_as_default_value_for_macro(&self) -> TokenStream {
         let ident = &self.ident;
         if self.is_optional {
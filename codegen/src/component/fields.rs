@@ -1,4 +1,6 @@
 use super::{props::PropsMeta, state::StateMeta};
+use crate::watch::watcher_ident;
+use heck::KebabCase;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use std::mem;
@@ -7,7 +9,8 @@ use syn::{
     parse::{Error, Parse, ParseStream, Result as ParseResult},
     punctuated::Punctuated,
     spanned::Spanned,
-    Attribute, Expr, Field, Fields, Ident, ItemStruct, Path, Token, Type, TypePath, Visibility,
+    Attribute, Expr, Field, Fields, Ident, ItemStruct, LitStr, Path, Token, Type, TypePath,
+    Visibility,
 };
 
 /// A field of a component. Stores all the struct metadata along with additional
@@ -69,11 +72,40 @@ impl ComponentField {
             FieldType::State
         };
 
-        let attr_arg = if field_type_attr.is_empty() {
+        let attr_arg: AttrArg = if field_type_attr.is_empty() {
             AttrArg::default()
         } else {
             syn::parse2(field_type_attr.remove(0).tts)?
         };
+        if attr_arg.init.is_some() && field_type == FieldType::Prop {
+            return Err(Error::new(
+                field.ident.span(),
+                "Only a `#[state]` can be initialized with `init`.",
+            ));
+        }
+        if attr_arg.persist.is_some() && field_type == FieldType::Prop {
+            return Err(Error::new(
+                field.ident.span(),
+                "Only a `#[state]` can be persisted.",
+            ));
+        }
+        if let Some(ref key) = attr_arg.persist {
+            if !cfg!(feature = "persist") {
+                return Err(Error::new(
+                    key.span(),
+                    "Persisting a state field requires the `persist` feature of `ruukh`.",
+                ));
+            }
+        }
+
+        let watch = Ident::new("watch", Span::call_site()).into();
+        if let Some(attr) = rest.iter().find(|attr| attr.path == watch) {
+            return Err(Error::new(
+                attr.span(),
+                "`#[watch]` is placed on the method which watches the field, like \
+                 `#[watch(field)] fn on_change(&self, old: &T, new: &T)`.",
+            ));
+        }
 
         Ok(ComponentField {
             attrs: rest,
@@ -167,10 +199,7 @@ impl ComponentField {
 
     pub fn to_field_assignment_as_default(&self) -> TokenStream {
         let ident = &self.ident;
-        if let AttrArg {
-            default: Some(DefaultArg::Expr(ref default)),
-        } = self.attr_arg
-        {
+        if let Some(DefaultArg::Expr(ref default)) = self.attr_arg.default {
             quote! {
                 #ident: #default
             }
@@ -188,12 +217,108 @@ impl ComponentField {
         }
     }
 
+    /// An expression which is true if the field changed from `old` to `new`,
+    /// as per the `compare` option of the field.
+    pub fn to_changed_check(&self, new: &TokenStream, old: &TokenStream) -> TokenStream {
+        let ident = &self.ident;
+        match self.attr_arg.compare {
+            None => quote! {
+                #new.#ident != #old.#ident
+            },
+            Some(CompareArg::Ptr) => quote! {
+                !std::ptr::eq(
+                    &*#new.#ident as *const _ as *const (),
+                    &*#old.#ident as *const _ as *const ()
+                )
+            },
+            Some(CompareArg::Fn(ref path)) => quote! {
+                !#path(&#new.#ident, &#old.#ident)
+            },
+            Some(CompareArg::NoCompare) => quote! {
+                true
+            },
+        }
+    }
+
+    /// Invokes the watcher of the field, i.e. the method of the component with
+    /// `#[watch(field)]`. It resolves to the empty watcher of `Unwatched`
+    /// otherwise, which needs to be declared in the scope.
+    pub fn to_watcher_call(&self, old: &TokenStream) -> TokenStream {
+        let ident = &self.ident;
+        let watcher = watcher_ident(ident);
+        quote! {
+            (&*self).#watcher(#old, &self.#ident);
+        }
+    }
+
+    /// Loads the persisted value of the field into the `state`, if persisted.
+    pub fn to_persisted_load(&self, state: &TokenStream) -> Option<TokenStream> {
+        self.attr_arg.persist.as_ref().map(|key| {
+            let ident = &self.ident;
+            quote! {
+                if let Some(value) = ruukh::storage::load(#key) {
+                    #state.#ident = value;
+                }
+            }
+        })
+    }
+
+    /// Saves the value of the field to the storage, if persisted.
+    pub fn to_persisted_save(&self) -> Option<TokenStream> {
+        self.attr_arg.persist.as_ref().map(|key| {
+            let ident = &self.ident;
+            quote! {
+                ruukh::storage::save(#key, &self.#ident);
+            }
+        })
+    }
+
+    /// The name of the attribute mapped onto this field in a Custom Element.
+    pub fn to_attribute_name(&self) -> String {
+        self.ident.to_string().to_kebab_case()
+    }
+
+    pub fn to_field_assignment_from_attribute(&self) -> TokenStream {
+        let ident = &self.ident;
+        let attribute_name = self.to_attribute_name();
+        let default = match self.attr_arg.default {
+            Some(DefaultArg::Expr(ref default)) => quote!(#default),
+            Some(DefaultArg::Default) => quote!(Default::default()),
+            // Wait for the required attribute, unless it may be absent.
+            None => quote! {
+                match ruukh::custom_element::FromAttribute::from_attribute(None) {
+                    Some(__absent__) => __absent__,
+                    None => return Ok(None),
+                }
+            },
+        };
+
+        quote! {
+            #ident: match __attribute__(#attribute_name) {
+                Some(__value__) => {
+                    ruukh::custom_element::convert_attribute(#attribute_name, __value__)?
+                }
+                None => #default,
+            }
+        }
+    }
+
+    /// The assignment of the initial state, which may be created from the
+    /// `props`.
+    pub fn to_field_assignment_as_initial(&self) -> TokenStream {
+        if let Some(ref init) = self.attr_arg.init {
+            let ident = &self.ident;
+            quote! {
+                #ident: #init
+            }
+        } else {
+            self.to_field_assignment_as_default()
+        }
+    }
+
     pub fn to_default_argument_for_macro(&self) -> TokenStream {
         let ident = &self.ident;
-        if let AttrArg {
-            default: Some(DefaultArg::Expr(ref default)),
-        } = self.attr_arg
-        {
+        if let Some(DefaultArg::Expr(ref default)) = self.attr_arg.default {
             quote! {
                 [ #ident = #default ]
             }
@@ -212,13 +337,23 @@ impl ComponentField {
 }
 
 custom_keyword!(default);
+custom_keyword!(init);
+custom_keyword!(persist);
+custom_keyword!(compare);
+custom_keyword!(no_compare);
 
 /// The argument passed with `#[prop]` or `#[state]` attributes.
 ///
-/// Can be `#[prop]`, `#[prop(default)]` or `#[prop(default = expr)]`.
+/// Can be `#[prop]`, `#[prop(default)]` or `#[prop(default = expr)]`, along
+/// with an optional `compare = "ptr"`, `compare = fn_path` or `no_compare`.
+/// A state may also be initialized from the props with `init = expr` and
+/// persisted in the storage with `persist = "key"`.
 #[derive(Default)]
 pub struct AttrArg {
     pub default: Option<DefaultArg>,
+    pub init: Option<Box<Expr>>,
+    pub persist: Option<LitStr>,
+    pub compare: Option<CompareArg>,
 }
 
 impl Parse for AttrArg {
@@ -230,16 +365,83 @@ impl Parse for AttrArg {
         let content;
         parenthesized!(content in input);
 
-        let default = if content.peek(default) {
-            Some(content.parse()?)
-        } else {
-            None
-        };
+        let mut attr_arg = AttrArg::default();
+        while !content.is_empty() {
+            if content.peek(default) {
+                if attr_arg.default.is_some() {
+                    return Err(content.error("`default` is already specified."));
+                }
+                attr_arg.default = Some(content.parse()?);
+            } else if content.peek(init) {
+                if attr_arg.init.is_some() {
+                    return Err(content.error("`init` is already specified."));
+                }
+                content.parse::<init>()?;
+                content.parse::<Token![=]>()?;
+                attr_arg.init = Some(Box::new(content.parse()?));
+            } else if content.peek(persist) {
+                if attr_arg.persist.is_some() {
+                    return Err(content.error("`persist` is already specified."));
+                }
+                content.parse::<persist>()?;
+                content.parse::<Token![=]>()?;
+                attr_arg.persist = Some(content.parse()?);
+            } else if content.peek(compare) || content.peek(no_compare) {
+                if attr_arg.compare.is_some() {
+                    return Err(content.error("the comparison is already specified."));
+                }
+                attr_arg.compare = Some(content.parse()?);
+            } else {
+                return Err(content.error(
+                    "expected `default`, `init`, `persist`, `compare` or `no_compare`.",
+                ));
+            }
+
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+        }
 
         if !input.is_empty() {
             return Err(input.error("expected `)`."));
         }
-        Ok(AttrArg { default })
+        Ok(attr_arg)
+    }
+}
+
+/// How a field is compared to find whether it changed. It is compared with
+/// `PartialEq` by default.
+pub enum CompareArg {
+    /// Compares the address pointed to, like for `Rc` or `Box`.
+    Ptr,
+    /// Compares with a `fn(&T, &T) -> bool` which returns whether equal.
+    Fn(Path),
+    /// Never compared and always treated as changed.
+    NoCompare,
+}
+
+impl Parse for CompareArg {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        if input.peek(no_compare) {
+            input.parse::<no_compare>()?;
+            return Ok(CompareArg::NoCompare);
+        }
+
+        input.parse::<compare>()?;
+        input.parse::<Token![=]>()?;
+        if input.peek(LitStr) {
+            let lit: LitStr = input.parse()?;
+            if lit.value() == "ptr" {
+                Ok(CompareArg::Ptr)
+            } else {
+                Err(Error::new(
+                    lit.span(),
+                    "expected `\"ptr\"` or a path to a comparison function.",
+                ))
+            }
+        } else {
+            Ok(CompareArg::Fn(input.parse()?))
+        }
     }
 }
 
@@ -259,3 +461,60 @@ impl Parse for DefaultArg {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_attr_arg() {
+        let arg: AttrArg = syn::parse_str("(default = 4)").unwrap();
+        assert!(arg.default.is_some());
+        assert!(arg.compare.is_none());
+    }
+
+    #[test]
+    fn should_parse_attr_arg_with_compare() {
+        let arg: AttrArg = syn::parse_str(r#"(default, compare = "ptr")"#).unwrap();
+        assert!(arg.default.is_some());
+        assert!(matches!(arg.compare, Some(CompareArg::Ptr)));
+
+        let arg: AttrArg = syn::parse_str("(compare = utils::same_len)").unwrap();
+        assert!(matches!(arg.compare, Some(CompareArg::Fn(_))));
+
+        let arg: AttrArg = syn::parse_str("(no_compare)").unwrap();
+        assert!(matches!(arg.compare, Some(CompareArg::NoCompare)));
+    }
+
+    #[test]
+    fn should_parse_attr_arg_with_init() {
+        let arg: AttrArg = syn::parse_str("(init = props.initial.clone(), default)").unwrap();
+        assert!(arg.init.is_some());
+        assert!(arg.default.is_some());
+        assert!(syn::parse_str::<AttrArg>("(init = 1, init = 2)").is_err());
+    }
+
+    #[test]
+    fn should_parse_attr_arg_with_persist() {
+        let arg: AttrArg = syn::parse_str(r#"(default, persist = "sidebar")"#).unwrap();
+        assert_eq!(arg.persist.unwrap().value(), "sidebar");
+        assert!(syn::parse_str::<AttrArg>("(persist = sidebar)").is_err());
+    }
+
+    #[cfg(not(feature = "persist"))]
+    #[test]
+    fn should_not_persist_without_the_feature() {
+        let mut component: ItemStruct = syn::parse_str(
+            r#"struct Sidebar { #[state(persist = "sidebar")] collapsed: bool }"#,
+        ).unwrap();
+        let parsed = ComponentField::parse_into_prop_and_state_meta(&mut component);
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    fn should_not_parse_invalid_attr_arg() {
+        assert!(syn::parse_str::<AttrArg>(r#"(compare = "deep")"#).is_err());
+        assert!(syn::parse_str::<AttrArg>("(no_compare, compare = \"ptr\")").is_err());
+        assert!(syn::parse_str::<AttrArg>("(unknown)").is_err());
+    }
+}
@@ -0,0 +1,197 @@
+use super::{ComponentArgs, ComponentMeta};
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parenthesized,
+    parse::{Error, Parse, ParseStream, Result as ParseResult},
+    parse_quote,
+    punctuated::Punctuated,
+    Attribute, Block, Generics, Ident, ItemStruct, ReturnType, Token, Type, Visibility,
+};
+
+/// A function component, which renders its props with the body of the
+/// function. Like:
+///
+/// ```ignore,compile_fail
+/// #[component]
+/// fn Badge(label: String, #[prop(default)] color: Color) -> Markup<Self> {
+///     ...
+/// }
+/// ```
+pub struct FnComponent {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    generics: Generics,
+    props: Punctuated<FnProp, Token![,]>,
+    output: ReturnType,
+    block: Block,
+}
+
+impl Parse for FnComponent {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![fn]>()?;
+        let ident: Ident = input.parse()?;
+        let mut generics: Generics = input.parse()?;
+
+        let content;
+        parenthesized!(content in input);
+        let props = content.parse_terminated(FnProp::parse)?;
+
+        let output: ReturnType = input.parse()?;
+        if let ReturnType::Default = output {
+            return Err(Error::new(
+                ident.span(),
+                "A function component must return `Markup<Self>`.",
+            ));
+        }
+        generics.where_clause = input.parse()?;
+        let block = input.parse()?;
+
+        Ok(FnComponent {
+            attrs,
+            vis,
+            ident,
+            generics,
+            props,
+            output,
+            block,
+        })
+    }
+}
+
+impl FnComponent {
+    /// Expands into a component struct with the props of the function, which
+    /// is rendered with the body of the function.
+    pub fn expand(self, args: ComponentArgs) -> ParseResult<TokenStream> {
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let prop_idents: Vec<_> = self.props.iter().map(|prop| &prop.ident).collect();
+        let output = &self.output;
+        let block = &self.block;
+
+        let component = ComponentMeta::parse(args, self.to_struct()?)?.expand();
+
+        Ok(quote! {
+            #component
+
+            impl #impl_generics Lifecycle for #ident #ty_generics #where_clause {}
+
+            impl #impl_generics Render for #ident #ty_generics #where_clause {
+                #[allow(unused_variables)]
+                fn render(&self) #output {
+                    let #ident { #(#prop_idents,)* .. } = self;
+                    #block
+                }
+            }
+        })
+    }
+
+    /// The component struct with a prop field for each parameter.
+    fn to_struct(&self) -> ParseResult<ItemStruct> {
+        let stateful = ["state", "computed"]
+            .iter()
+            .map(|name| Ident::new(name, Span::call_site()).into())
+            .collect::<Vec<syn::Path>>();
+        let fields = self
+            .props
+            .iter()
+            .map(|prop| {
+                if let Some(attr) = prop
+                    .attrs
+                    .iter()
+                    .find(|attr| stateful.contains(&attr.path))
+                {
+                    return Err(Error::new(
+                        prop.ident.span(),
+                        format!(
+                            "A function component cannot have a `#[{}]` field. Use a \
+                             struct component instead.",
+                            attr.path.segments[0].ident
+                        ),
+                    ));
+                }
+                let attrs = &prop.attrs;
+                let ident = &prop.ident;
+                let ty = &prop.ty;
+                Ok(quote! {
+                    #(#attrs)*
+                    #ident: #ty
+                })
+            }).collect::<ParseResult<Vec<_>>>()?;
+
+        let attrs = &self.attrs;
+        let vis = &self.vis;
+        let ident = &self.ident;
+        let generics = &self.generics;
+        let where_clause = &self.generics.where_clause;
+        Ok(parse_quote! {
+            #(#attrs)*
+            #vis struct #ident #generics #where_clause {
+                #(#fields),*
+            }
+        })
+    }
+}
+
+/// A parameter of a function component, which is a prop.
+struct FnProp {
+    attrs: Vec<Attribute>,
+    ident: Ident,
+    ty: Type,
+}
+
+impl Parse for FnProp {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        Ok(FnProp { attrs, ident, ty })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_fn_component() {
+        let parsed: FnComponent = syn::parse_str(
+            "fn Badge(label: String, #[prop(default)] color: Color) -> Markup<Self> {
+                html! { { label } }
+            }",
+        ).unwrap();
+
+        assert_eq!(parsed.ident, "Badge");
+        assert_eq!(parsed.props.len(), 2);
+        assert_eq!(parsed.props[1].attrs.len(), 1);
+    }
+
+    #[test]
+    fn should_not_parse_fn_component_without_markup() {
+        let parsed: ParseResult<FnComponent> = syn::parse_str("fn Badge(label: String) {}");
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    fn should_convert_fn_component_to_struct() {
+        let parsed: FnComponent = syn::parse_str(
+            "pub fn Badge(label: String) -> Markup<Self> { html! {} }",
+        ).unwrap();
+        let struct_ = parsed.to_struct().unwrap();
+
+        assert_eq!(struct_.ident, "Badge");
+        assert_eq!(struct_.fields.iter().count(), 1);
+    }
+
+    #[test]
+    fn should_not_allow_state_in_fn_component() {
+        let parsed: FnComponent = syn::parse_str(
+            "fn Badge(#[state] count: i32) -> Markup<Self> { html! {} }",
+        ).unwrap();
+        assert!(parsed.to_struct().is_err());
+    }
+}
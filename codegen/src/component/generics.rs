@@ -0,0 +1,63 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Error, Result as ParseResult},
+    parse_quote,
+    spanned::Spanned,
+    GenericParam, Generics,
+};
+
+/// Verifies that only type parameters are declared on the component and
+/// bounds them with `'static`, which is required of any `Component`.
+pub fn normalize(generics: &mut Generics) -> ParseResult<()> {
+    for param in generics.params.iter_mut() {
+        match param {
+            GenericParam::Type(ref mut ty) => ty.bounds.push(parse_quote!('static)),
+            GenericParam::Lifetime(_) => {
+                return Err(Error::new(
+                    param.span(),
+                    "Lifetime parameters not allowed on a component.",
+                ))
+            }
+            GenericParam::Const(_) => {
+                return Err(Error::new(
+                    param.span(),
+                    "Const parameters not allowed on a component.",
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Prepends the `RCTX: Render` parameter of the render context to the
+/// generics, used by the event props.
+pub fn with_render_context(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    generics.params.insert(0, parse_quote!(RCTX: Render));
+    generics
+}
+
+/// A marker field for the generated types which may not use all the type
+/// parameters of the component.
+pub fn phantom_field(generics: &Generics) -> Option<TokenStream> {
+    if generics.params.is_empty() {
+        None
+    } else {
+        let tys = generics.type_params().map(|param| &param.ident);
+        Some(quote! {
+            __phantom__: std::marker::PhantomData<fn() -> (#(#tys,)*)>
+        })
+    }
+}
+
+/// The assignment to the marker field, if any.
+pub fn phantom_assignment(generics: &Generics) -> Option<TokenStream> {
+    if generics.params.is_empty() {
+        None
+    } else {
+        Some(quote! {
+            __phantom__: std::marker::PhantomData
+        })
+    }
+}
@@ -1,8 +1,8 @@
-use super::fields::ComponentField;
+use super::{fields::ComponentField, generics};
 use crate::suffix::PROPS_SUFFIX;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Ident, ItemStruct, Visibility};
+use syn::{Generics, Ident, ItemStruct, Visibility};
 
 /// Stores the list of prop fields.
 pub struct PropsMeta {
@@ -12,6 +12,8 @@ pub struct PropsMeta {
     pub component_ident: Ident,
     /// Visiblilty of the component.
     pub vis: Visibility,
+    /// Generics of the component.
+    pub generics: Generics,
     /// List of prop fields.
     pub fields: Vec<ComponentField>,
 }
@@ -25,6 +27,7 @@ impl PropsMeta {
             ),
             component_ident: component.ident.clone(),
             vis: component.vis.clone(),
+            generics: component.generics.clone(),
             fields,
         }
     }
@@ -47,6 +50,13 @@ impl PropsMeta {
         self.fields.iter().map(ComponentField::to_ident).collect()
     }
 
+    /// The props type along with the generics of the component.
+    pub fn to_type(&self) -> TokenStream {
+        let ident = &self.ident;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        quote!(#ident #ty_generics)
+    }
+
     pub fn create_props_struct_and_macro(&self) -> TokenStream {
         if self.fields.is_empty() {
             self.create_void_props_macro()
@@ -58,13 +68,16 @@ impl PropsMeta {
     fn _create_props_struct_and_macro(&self) -> TokenStream {
         let ident = &self.ident;
         let vis = &self.vis;
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
         let fields = self.expand_fields_with(ComponentField::to_struct_field);
+        let phantom_field = generics::phantom_field(&self.generics);
 
         let props_macro = self.create_props_macro();
 
         quote! {
-            #vis struct #ident {
-                #(#fields),*
+            #vis struct #ident #impl_generics #where_clause {
+                #(#fields,)*
+                #phantom_field
             }
 
             #props_macro
@@ -83,6 +96,7 @@ impl PropsMeta {
         let first = next_idents.remove(0);
         next_idents.push(quote!(@finish));
         let internal_macro_ident = self.internal_macro_ident();
+        let phantom_assignment = generics::phantom_assignment(&self.generics);
 
         let match_hands = field_idents
             .iter()
@@ -92,26 +106,43 @@ impl PropsMeta {
                 quote!{
                     (
                         @#cur
+                        spread = [{ $($spread:tt)* }]
                         arguments = [{ $($args:tt)* }]
                         tokens = [{ [#cur = $val:expr] $($rest:tt)* }]
                     ) => {
                         #internal_macro_ident!(
                             @#next
+                            spread = [{ $($spread)* }]
                             arguments = [{ $($args)* [#cur = $val] }]
                             tokens = [{ $($rest)* }]
                         );
                     },
                     (
                         @#cur
+                        spread = [{ }]
                         arguments = [{ $($args:tt)* }]
                         tokens = [{ $($rest:tt)* }]
                     ) => {
                         #internal_macro_ident!(
                             @#next
+                            spread = [{ }]
                             arguments = [{ $($args)* #default }]
                             tokens = [{ $($rest)* }]
                         );
                     },
+                    (
+                        @#cur
+                        spread = [{ $spread:ident }]
+                        arguments = [{ $($args:tt)* }]
+                        tokens = [{ $($rest:tt)* }]
+                    ) => {
+                        #internal_macro_ident!(
+                            @#next
+                            spread = [{ $spread }]
+                            arguments = [{ $($args)* [#cur = ::std::clone::Clone::clone(&$spread.#cur)] }]
+                            tokens = [{ $($rest)* }]
+                        );
+                    },
                 }
             });
 
@@ -120,15 +151,18 @@ impl PropsMeta {
                 #(#match_hands)*
                 (
                     @@finish
+                    spread = [{ $($spread:tt)* }]
                     arguments = [{ $([$key:ident = $val:expr])* }]
                     tokens = [{ }]
                 ) => {
                     #ident {
-                        $($key: $val),*
+                        $($key: $val,)*
+                        #phantom_assignment
                     }
                 },
                 (
                     @@finish
+                    spread = [{ $($spread:tt)* }]
                     arguments = [{ $($tt:tt)* }]
                     tokens = [{ [$key:ident = $val:expr] $($rem:tt)* }]
                 ) => {
@@ -136,12 +170,26 @@ impl PropsMeta {
                 }
             }
 
-            #vis macro #ident($($key:ident: $val:expr),*) {
-                #internal_macro_ident!(
-                    @#first
-                    arguments = [{ }]
-                    tokens = [{ $([$key = $val])* }]
-                );
+            // The remaining props are cloned from the spread props, which are
+            // only borrowed.
+            #vis macro #ident {
+                (..$spread:expr; $($key:ident: $val:expr),*) => {{
+                    let __spread__ = &$spread;
+                    #internal_macro_ident!(
+                        @#first
+                        spread = [{ __spread__ }]
+                        arguments = [{ }]
+                        tokens = [{ $([$key = $val])* }]
+                    )
+                }},
+                ($($key:ident: $val:expr),*) => {
+                    #internal_macro_ident!(
+                        @#first
+                        spread = [{ }]
+                        arguments = [{ }]
+                        tokens = [{ $([$key = $val])* }]
+                    );
+                },
             }
         }
     }
@@ -174,6 +222,38 @@ impl PropsMeta {
         }
     }
 
+    pub fn impl_from_attributes_trait(&self) -> Option<TokenStream> {
+        if self.fields.is_empty() {
+            None
+        } else {
+            let ident = &self.ident;
+            let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+            let attribute_names = self.fields.iter().map(ComponentField::to_attribute_name);
+            let field_assignments =
+                self.expand_fields_with(ComponentField::to_field_assignment_from_attribute);
+            let phantom_assignment = generics::phantom_assignment(&self.generics);
+
+            Some(quote! {
+                impl #impl_generics ruukh::custom_element::FromAttributes
+                    for #ident #ty_generics #where_clause
+                {
+                    const OBSERVED_ATTRIBUTES: &'static [&'static str] = &[
+                        #(#attribute_names),*
+                    ];
+
+                    fn from_attributes(
+                        __attribute__: &dyn Fn(&str) -> Option<String>
+                    ) -> Result<Option<Self>, ruukh::custom_element::InvalidAttribute> {
+                        Ok(Some(#ident {
+                            #(#field_assignments,)*
+                            #phantom_assignment
+                        }))
+                    }
+                }
+            })
+        }
+    }
+
     fn internal_macro_ident(&self) -> Ident {
         Ident::new(
             &format!("__new_{}_internal__", self.ident),
@@ -1,13 +1,15 @@
-use super::fields::ComponentField;
+use super::{fields::ComponentField, generics};
 use crate::suffix::STATE_SUFFIX;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Ident, ItemStruct};
+use syn::{Generics, Ident, ItemStruct};
 
 /// Stores the list of state fields.
 pub struct StateMeta {
     /// Ident of state struct.
     pub ident: Ident,
+    /// Generics of the component.
+    pub generics: Generics,
     /// List of state fields.
     pub fields: Vec<ComponentField>,
 }
@@ -19,6 +21,7 @@ impl StateMeta {
                 &format!("{}{}", component.ident, STATE_SUFFIX),
                 Span::call_site(),
             ),
+            generics: component.generics.clone(),
             fields,
         }
     }
@@ -41,24 +44,79 @@ impl StateMeta {
         self.fields.iter().map(ComponentField::to_ident).collect()
     }
 
+    /// The state type along with the generics of the component.
+    pub fn to_type(&self) -> TokenStream {
+        let ident = &self.ident;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        quote!(#ident #ty_generics)
+    }
+
+    /// The `initial_state` fn of the component, if any state field is
+    /// initialized from the props.
+    pub fn impl_fn_initial_state(&self) -> Option<TokenStream> {
+        if self.fields.iter().all(|f| f.attr_arg.init.is_none()) {
+            return None;
+        }
+
+        let ident = &self.ident;
+        let init_fields = self.expand_fields_with(ComponentField::to_field_assignment_as_initial);
+        let phantom_assignment = generics::phantom_assignment(&self.generics);
+
+        Some(quote! {
+            #[allow(unused_variables)]
+            fn initial_state(props: &Self::Props) -> Self::State {
+                #ident {
+                    #(#init_fields,)*
+                    #phantom_assignment
+                }
+            }
+        })
+    }
+
+    /// Implements `Clone` on the state struct, without bounding the type
+    /// parameters of the component with `Clone` unlike the derive.
+    pub fn impl_clone_trait(&self) -> TokenStream {
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let idents = &self.to_field_idents();
+        let idents2 = idents;
+        let phantom_assignment = generics::phantom_assignment(&self.generics);
+
+        quote! {
+            impl #impl_generics Clone for #ident #ty_generics #where_clause {
+                fn clone(&self) -> Self {
+                    #ident {
+                        #(#idents: self.#idents2.clone(),)*
+                        #phantom_assignment
+                    }
+                }
+            }
+        }
+    }
+
     pub fn create_state_struct(&self) -> Option<TokenStream> {
         if self.fields.is_empty() {
             None
         } else {
             let ident = &self.ident;
+            let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
             let fields = self.expand_fields_with(ComponentField::to_struct_field);
             let def_fields =
                 self.expand_fields_with(ComponentField::to_field_assignment_as_default);
+            let phantom_field = generics::phantom_field(&self.generics);
+            let phantom_assignment = generics::phantom_assignment(&self.generics);
 
             Some(quote! {
-                struct #ident {
-                    #(#fields),*
+                struct #ident #impl_generics #where_clause {
+                    #(#fields,)*
+                    #phantom_field
                 }
 
-                impl Default for #ident {
+                impl #impl_generics Default for #ident #ty_generics #where_clause {
                     fn default() -> Self {
                         #ident {
-                            #(#def_fields),*
+                            #(#def_fields,)*
+                            #phantom_assignment
                         }
                     }
                 }
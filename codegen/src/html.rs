@@ -2,7 +2,8 @@
 //!
 //! ROOT -> ITEM ITEM
 //!
-//! ITEM -> ELEMENT ITEM | EXPR_BLOCK ITEM | TEXT ITEM | EPS
+//! ITEM -> ELEMENT ITEM | EXPR_BLOCK ITEM | TEXT ITEM | IF ITEM | FOR ITEM |
+//!     MATCH ITEM | EPS
 //!
 //! ELEMENT ->
 //! <TAGNAME ATTRIBUTES>
@@ -15,28 +16,57 @@
 //!
 //! EXPR_BLOCK -> { EXPR }
 //!
+//! IF -> if EXPR { ROOT } ELSE
+//!
+//! ELSE -> else IF | else { ROOT } | EPS
+//!
+//! FOR -> for PAT in EXPR { ROOT }
+//!
+//! MATCH -> match EXPR { ARMS }
+//!
+//! ARMS -> PAT GUARD => { ROOT } OPTIONAL_COMMA ARMS | PAT GUARD => ITEM , ARMS |
+//!     PAT GUARD => ITEM | EPS
+//!
+//! GUARD -> if EXPR | EPS
+//!
+//! OPTIONAL_COMMA -> , | EPS
+//!
 //! TAGNAME -> DASHED_IDENT
 //!
 //! ATTRIBUTES -> ATTRIBUTE ATTRIBUTES | EPS
 //!
-//! ATTRIBUTE -> OPTIONAL_AT DASHED_IDENT = { EXPR }
+//! ATTRIBUTE -> OPTIONAL_AT DASHED_IDENT = { EXPR } | OPTIONAL_AT DASHED_IDENT = LIT |
+//!     OPTIONAL_AT { IDENT } | DASHED_IDENT | .. SPREAD
+//!
+//! SPREAD -> { EXPR } | PATH
+//!
+//! PATH -> IDENT OPTIONAL_ARGS . PATH | IDENT OPTIONAL_ARGS :: PATH | IDENT OPTIONAL_ARGS
+//!
+//! OPTIONAL_ARGS -> ( EXPRS ) | EPS
 //!
 //! OPTIONAL_AT -> @ | EPS
 //!
 //! DASHED_IDENT -> IDENT-DASHED_IDENT | IDENT
 //!
-//! N.B. EPS is Epsilon and IDENT & EXPR are Rust constructs.
+//! N.B. EPS is Epsilon and IDENT, EXPR, PAT & LIT are Rust constructs. The EXPR
+//! after `if`, `in` & `match` may not contain braces unless parenthesized.
 
-use self::element::{HtmlElement, KeyAttribute};
-use proc_macro2::TokenStream;
+use self::{
+    control::{HtmlFor, HtmlIf, HtmlMatch},
+    element::{ClosingTag, HtmlElement, KeyAttribute},
+};
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{
-    parse::{Parse, ParseStream, Result as ParseResult},
-    token, Block as RustExpressionBlock, LitStr, Token,
+    parse::{Error, Parse, ParseStream, Result as ParseResult},
+    token, Block as RustExpressionBlock, LitStr, Stmt, Token,
 };
 
+mod control;
 mod element;
 mod kw;
+mod spec;
+mod validate;
 
 pub struct HtmlRoot {
     pub items: Vec<HtmlItems>,
@@ -50,6 +80,13 @@ impl Parse for HtmlRoot {
         while !input.is_empty() {
             // Encounters an end tag.
             if input.peek(Token![<]) && input.peek2(Token![/]) {
+                let closing_tag: ClosingTag = input.fork().parse()?;
+                if closing_tag.is_void() {
+                    return Err(Error::new(
+                        closing_tag.tag_name.span(),
+                        "a void element cannot have children or a closing tag.",
+                    ));
+                }
                 break;
             }
             ungrouped_items.push(input.parse()?);
@@ -159,6 +196,9 @@ pub enum HtmlItem {
     Element(Box<HtmlElement>),
     ExpressionBlock(RustExpressionBlock),
     Text(Text),
+    If(Box<HtmlIf>),
+    For(Box<HtmlFor>),
+    Match(Box<HtmlMatch>),
 }
 
 impl Parse for HtmlItem {
@@ -170,6 +210,12 @@ impl Parse for HtmlItem {
             Ok(HtmlItem::ExpressionBlock(input.parse()?))
         } else if lookahead1.peek(LitStr) {
             Ok(HtmlItem::Text(input.parse()?))
+        } else if lookahead1.peek(Token![if]) {
+            Ok(HtmlItem::If(Box::new(input.parse()?)))
+        } else if lookahead1.peek(Token![for]) {
+            Ok(HtmlItem::For(Box::new(input.parse()?)))
+        } else if lookahead1.peek(Token![match]) {
+            Ok(HtmlItem::Match(Box::new(input.parse()?)))
         } else {
             Err(lookahead1.error())
         }
@@ -178,33 +224,45 @@ impl Parse for HtmlItem {
 
 impl HtmlItem {
     fn expand(&self) -> TokenStream {
-        let expanded = match self {
+        let expanded = self.expand_node();
+        if let Some(key) = self.key() {
+            let key_expanded = key.expand();
+            quote! {
+                #key_expanded, #expanded
+            }
+        } else {
+            expanded
+        }
+    }
+
+    /// Expands the item as a node, ignoring its key.
+    fn expand_node(&self) -> TokenStream {
+        match self {
             HtmlItem::Element(ref element) => {
                 let expanded = element.expand();
                 quote! {
                     ruukh::vdom::VNode::from(#expanded)
                 }
             }
-            HtmlItem::ExpressionBlock(ref block) => {
-                quote! {
+            HtmlItem::ExpressionBlock(ref block) => match block.stmts.as_slice() {
+                // A lone expression is passed as is, as the braces around it
+                // would be linted as unnecessary.
+                [Stmt::Expr(expr)] => quote! {
+                    ruukh::vdom::VNode::from(#expr)
+                },
+                _ => quote! {
                     ruukh::vdom::VNode::from(#block)
-                }
-            }
+                },
+            },
             HtmlItem::Text(ref text) => {
                 let string = &text.content;
                 quote! {
                     ruukh::vdom::VNode::from(ruukh::vdom::vtext::VText::text(#string))
                 }
             }
-        };
-
-        if let Some(key) = self.key() {
-            let key_expanded = key.expand();
-            quote! {
-                #key_expanded, #expanded
-            }
-        } else {
-            expanded
+            HtmlItem::If(ref if_) => if_.expand(),
+            HtmlItem::For(ref for_) => for_.expand(),
+            HtmlItem::Match(ref match_) => match_.expand(),
         }
     }
 
@@ -218,17 +276,20 @@ impl HtmlItem {
 
 pub struct Text {
     pub content: String,
+    pub span: Span,
 }
 
 impl Parse for Text {
     fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
         let mut content = Vec::<LitStr>::new();
+        let span = input.cursor().span();
 
         while input.peek(LitStr) {
             content.push(input.parse()?);
         }
 
         Ok(Text {
+            span,
             content: content
                 .into_iter()
                 .map(|lit| lit.value())
@@ -268,6 +329,62 @@ mod test {
         ).unwrap();
     }
 
+    #[test]
+    fn should_parse_html_with_if_else() {
+        let _: HtmlRoot = syn::parse_str(
+            r#"
+            if self.visible {
+                <div>"Visible"</div>
+            } else if let Some(reason) = self.reason {
+                "Hidden because "{ reason }
+            } else {
+                "Hidden"
+            }
+            if self.visible { "Shown" }
+            "#,
+        ).unwrap();
+    }
+
+    #[test]
+    fn should_parse_html_with_for() {
+        let _: HtmlRoot = syn::parse_str(
+            r#"
+            <ul>
+                for (index, item) in self.items.iter().enumerate() {
+                    <li key={index}>{ item }</li>
+                }
+            </ul>
+            "#,
+        ).unwrap();
+    }
+
+    #[test]
+    fn should_parse_html_with_match() {
+        let _: HtmlRoot = syn::parse_str(
+            r#"
+            match self.status {
+                Status::Loading => "Loading...",
+                Status::Failed(ref err) if err.is_fatal() => {
+                    <div>"Failed: "{ err }</div>
+                }
+                Status::Done | Status::Idle => <span>"Done"</span>
+            }
+            "#,
+        ).unwrap();
+    }
+
+    #[test]
+    fn should_not_parse_if_without_block() {
+        let res: ParseResult<HtmlRoot> = syn::parse_str("if self.visible");
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn should_not_parse_children_of_void_element() {
+        let res: ParseResult<HtmlRoot> = syn::parse_str(r#"<div><input>"Text"</input></div>"#);
+        assert!(res.is_err());
+    }
+
     #[test]
     fn should_parse_text() {
         let text: Text =
@@ -0,0 +1,252 @@
+//! Control-flow constructs usable directly within the markup.
+use super::{HtmlItem, HtmlItems, HtmlRoot};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use syn::{
+    braced,
+    parse::{Parse, ParseStream, Result as ParseResult},
+    punctuated::Punctuated,
+    token, Expr, Pat, Token,
+};
+
+/// Parses the tokens up till the next brace as an expression. Used for the
+/// expressions after `if`, `in` & `match` which are followed by the markup
+/// block.
+///
+/// A brace within the expression needs to be wrapped in parentheses, just
+/// like the struct literals within those expressions in Rust.
+fn parse_expr_before_brace(input: ParseStream<'_>) -> ParseResult<Expr> {
+    let mut tokens = TokenStream::new();
+    while !input.peek(token::Brace) {
+        if input.is_empty() {
+            return Err(input.error("expected `{`"));
+        }
+        let tt: TokenTree = input.parse()?;
+        tokens.extend(Some(tt));
+    }
+    syn::parse2(tokens)
+}
+
+/// Parses the contents of the braces as markup.
+fn parse_braced_root(input: ParseStream<'_>) -> ParseResult<HtmlRoot> {
+    let content;
+    braced!(content in input);
+    content.parse()
+}
+
+/// `if cond { ... } else if cond { ... } else { ... }`, where `cond` may also
+/// be a `let` binding.
+pub struct HtmlIf {
+    pub cond: Expr,
+    pub then_branch: HtmlRoot,
+    pub else_branch: Option<HtmlElse>,
+}
+
+pub enum HtmlElse {
+    If(Box<HtmlIf>),
+    Else(HtmlRoot),
+}
+
+impl Parse for HtmlIf {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        input.parse::<Token![if]>()?;
+        let cond = parse_expr_before_brace(input)?;
+        let then_branch = parse_braced_root(input)?;
+        let else_branch = if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            if input.peek(Token![if]) {
+                Some(HtmlElse::If(Box::new(input.parse()?)))
+            } else {
+                Some(HtmlElse::Else(parse_braced_root(input)?))
+            }
+        } else {
+            None
+        };
+
+        Ok(HtmlIf {
+            cond,
+            then_branch,
+            else_branch,
+        })
+    }
+}
+
+impl HtmlIf {
+    pub fn expand(&self) -> TokenStream {
+        let cond = &self.cond;
+        let then_branch = self.then_branch.expand();
+        let else_branch = match self.else_branch {
+            Some(HtmlElse::If(ref else_if)) => else_if.expand(),
+            Some(HtmlElse::Else(ref root)) => root.expand(),
+            None => quote! {
+                ruukh::vdom::VNode::None
+            },
+        };
+        quote! {
+            if #cond {
+                #then_branch
+            } else {
+                #else_branch
+            }
+        }
+    }
+}
+
+/// `for pat in expr { ... }`
+pub struct HtmlFor {
+    pub pat: Pat,
+    pub expr: Expr,
+    pub body: HtmlRoot,
+}
+
+impl Parse for HtmlFor {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        input.parse::<Token![for]>()?;
+        let pat = input.parse()?;
+        input.parse::<Token![in]>()?;
+        Ok(HtmlFor {
+            pat,
+            expr: parse_expr_before_brace(input)?,
+            body: parse_braced_root(input)?,
+        })
+    }
+}
+
+impl HtmlFor {
+    pub fn expand(&self) -> TokenStream {
+        let pat = &self.pat;
+        let expr = &self.expr;
+
+        // A single keyed item in the body makes the list a keyed one.
+        if let Some(item) = self.keyed_item() {
+            let key = item.key().unwrap().expand();
+            let node = item.expand_node();
+            quote! {
+                ruukh::vdom::VNode::from(ruukh::vdom::vlist::VList::from({
+                    let mut map = ruukh::reexports::IndexMap::with_hasher(
+                        ruukh::reexports::FnvBuildHasher::default()
+                    );
+                    for #pat in #expr {
+                        map.insert(#key, #node);
+                    }
+                    map
+                }))
+            }
+        } else {
+            let body = self.body.expand();
+            quote! {
+                ruukh::vdom::VNode::from(ruukh::vdom::vlist::VList::from({
+                    let mut list = Vec::new();
+                    for #pat in #expr {
+                        list.push(#body);
+                    }
+                    list
+                }))
+            }
+        }
+    }
+
+    fn keyed_item(&self) -> Option<&HtmlItem> {
+        match self.body.items.as_slice() {
+            [HtmlItems::Keyed(ref items)] if items.len() == 1 => items.first(),
+            _ => None,
+        }
+    }
+}
+
+/// `match expr { pat => { ... }, pat if guard => <item/>, ... }`
+pub struct HtmlMatch {
+    pub expr: Expr,
+    pub arms: Vec<HtmlArm>,
+}
+
+impl Parse for HtmlMatch {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        input.parse::<Token![match]>()?;
+        let expr = parse_expr_before_brace(input)?;
+        let content;
+        braced!(content in input);
+        let mut arms = vec![];
+        while !content.is_empty() {
+            arms.push(content.parse()?);
+        }
+        Ok(HtmlMatch { expr, arms })
+    }
+}
+
+impl HtmlMatch {
+    pub fn expand(&self) -> TokenStream {
+        let expr = &self.expr;
+        let arms: Vec<_> = self.arms.iter().map(HtmlArm::expand).collect();
+        quote! {
+            match #expr {
+                #(#arms)*
+            }
+        }
+    }
+}
+
+/// An arm of the match, whose body is either markup within braces or a single
+/// item.
+pub struct HtmlArm {
+    pub pats: Punctuated<Pat, Token![|]>,
+    pub guard: Option<Expr>,
+    pub body: HtmlArmBody,
+}
+
+pub enum HtmlArmBody {
+    Block(HtmlRoot),
+    Item(Box<HtmlItem>),
+}
+
+impl Parse for HtmlArm {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        input.parse::<Option<Token![|]>>()?;
+        let mut pats = Punctuated::new();
+        pats.push_value(input.parse()?);
+        while input.peek(Token![|]) {
+            pats.push_punct(input.parse()?);
+            pats.push_value(input.parse()?);
+        }
+        let guard = if input.peek(Token![if]) {
+            input.parse::<Token![if]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        input.parse::<Token![=>]>()?;
+        let body = if input.peek(token::Brace) {
+            let body = HtmlArmBody::Block(parse_braced_root(input)?);
+            input.parse::<Option<Token![,]>>()?;
+            body
+        } else {
+            let body = HtmlArmBody::Item(Box::new(input.parse()?));
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+            body
+        };
+
+        Ok(HtmlArm { pats, guard, body })
+    }
+}
+
+impl HtmlArm {
+    fn expand(&self) -> TokenStream {
+        let pats = &self.pats;
+        let guard = self.guard.as_ref().map(|guard| {
+            quote! {
+                if #guard
+            }
+        });
+        let body = match self.body {
+            HtmlArmBody::Block(ref root) => root.expand(),
+            HtmlArmBody::Item(ref item) => item.expand_node(),
+        };
+        quote! {
+            #pats #guard => {
+                #body
+            }
+        }
+    }
+}
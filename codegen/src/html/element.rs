@@ -1,15 +1,18 @@
 use super::kw;
+use super::spec::{self, Content};
 use super::HtmlRoot;
 use crate::suffix::{EVENT_SUFFIX, PROPS_SUFFIX};
 use heck::{CamelCase, KebabCase, SnakeCase};
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::{quote, quote_spanned};
 use syn::{
     braced,
+    ext::IdentExt,
     parse::{Error, Parse, ParseStream, Result as ParseResult},
     punctuated::Punctuated,
+    parse_quote,
     spanned::Spanned,
-    token, Token, {Expr, Ident},
+    token, AngleBracketedGenericArguments, Token, {Expr, ExprLit, Ident, Lit},
 };
 
 pub enum HtmlElement {
@@ -19,7 +22,7 @@ pub enum HtmlElement {
 
 impl Parse for HtmlElement {
     fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
-        if kw::is_self_closing(&input) {
+        if kw::is_self_closing(&input) || is_self_closing_component(input) {
             Ok(HtmlElement::SelfClosing(input.parse()?))
         } else {
             Ok(HtmlElement::Normal(input.parse()?))
@@ -27,6 +30,14 @@ impl Parse for HtmlElement {
     }
 }
 
+/// Whether it is a component tag closed with a slash, like `<Badge />`.
+fn is_self_closing_component(input: ParseStream<'_>) -> bool {
+    match input.fork().parse::<SelfClosingTag>() {
+        Ok(tag) => tag.tag_name.is_component() && tag.slash.is_some(),
+        Err(_) => false,
+    }
+}
+
 impl HtmlElement {
     pub fn expand(&self) -> TokenStream {
         match self {
@@ -41,6 +52,20 @@ impl HtmlElement {
             HtmlElement::SelfClosing(ref el) => el.key(),
         }
     }
+
+    /// The tag name, the prop attributes and the child markup if any.
+    pub fn parts(&self) -> (&TagName, &[HtmlAttribute], Option<&HtmlRoot>) {
+        match self {
+            HtmlElement::Normal(ref el) => (
+                &el.opening_tag.tag_name,
+                &el.opening_tag.prop_attributes,
+                Some(&el.child),
+            ),
+            HtmlElement::SelfClosing(ref el) => {
+                (&el.tag.tag_name, &el.tag.prop_attributes, None)
+            }
+        }
+    }
 }
 
 pub struct NormalHtmlElement {
@@ -64,9 +89,10 @@ impl Parse for NormalHtmlElement {
 
         let not_same = match (&opening_tag.tag_name, &closing_tag.tag_name) {
             (TagName::Tag { name: ref op, .. }, TagName::Tag { name: ref cl, .. }) => op != cl,
-            (TagName::Component { ident: ref op }, TagName::Component { ident: ref cl }) => {
-                op != cl
-            }
+            (
+                TagName::Component { ident: ref op, .. },
+                TagName::Component { ident: ref cl, .. },
+            ) => op != cl,
             _ => true,
         };
 
@@ -122,6 +148,7 @@ pub struct OpeningTag {
     pub lt: Token![<],
     pub tag_name: TagName,
     pub key: Option<KeyAttribute>,
+    pub spread: Option<SpreadAttribute>,
     pub prop_attributes: Vec<HtmlAttribute>,
     pub event_attributes: Vec<HtmlAttribute>,
     pub gt: Token![>],
@@ -132,11 +159,17 @@ impl Parse for OpeningTag {
         let lt = input.parse()?;
         let tag_name = input.parse()?;
         let mut key = None;
+        let mut spread = None;
 
         let mut attributes: Vec<HtmlAttribute> = vec![];
         while !input.peek(Token![>]) {
             if input.peek(kw::key) {
                 key = Some(input.parse()?);
+            } else if input.peek(Token![..]) {
+                if spread.is_some() {
+                    return Err(input.error("only a single spread is allowed on a tag."));
+                }
+                spread = Some(input.parse()?);
             } else {
                 attributes.push(input.parse()?);
             }
@@ -155,6 +188,7 @@ impl Parse for OpeningTag {
             lt,
             tag_name,
             key,
+            spread,
             prop_attributes,
             event_attributes,
             gt,
@@ -166,11 +200,8 @@ impl OpeningTag {
     fn expand_with(&self, child: &TokenStream) -> TokenStream {
         match self.tag_name {
             TagName::Tag { ref name, .. } => {
-                let prop_attributes: Vec<_> = self
-                    .prop_attributes
-                    .iter()
-                    .map(|p| p.expand_as_prop_attribute().unwrap())
-                    .collect();
+                let prop_attributes =
+                    expand_element_attributes(&self.prop_attributes, self.spread.as_ref());
                 let event_attributes: Vec<_> = self
                     .event_attributes
                     .iter()
@@ -180,35 +211,75 @@ impl OpeningTag {
                 quote! {
                     ruukh::vdom::velement::VElement::new(
                         #name,
-                        vec![#(#prop_attributes),*],
+                        #prop_attributes,
                         vec![#(#event_attributes),*],
                         #child
                     )
                 }
             }
-            TagName::Component { ref ident } => {
-                let prop_attributes: Vec<_> = self
-                    .prop_attributes
-                    .iter()
-                    .map(|p| p.expand_as_named_arg())
-                    .collect();
+            TagName::Component {
+                ref ident,
+                ref generics,
+            } => expand_component(
+                ident,
+                generics.as_ref(),
+                self.spread.as_ref(),
+                &self.prop_attributes,
+                &self.event_attributes,
+            ),
+        }
+    }
+}
 
-                let event_attributes: Vec<_> = self
-                    .event_attributes
-                    .iter()
-                    .map(|e| e.expand_as_named_arg())
-                    .collect();
+/// Expands a component along with its props & events.
+fn expand_component(
+    ident: &Ident,
+    generics: Option<&AngleBracketedGenericArguments>,
+    spread: Option<&SpreadAttribute>,
+    prop_attributes: &[HtmlAttribute],
+    event_attributes: &[HtmlAttribute],
+) -> TokenStream {
+    let prop_attributes: Vec<_> = prop_attributes
+        .iter()
+        .map(|p| p.expand_as_named_arg())
+        .collect();
+
+    let event_attributes: Vec<_> = event_attributes
+        .iter()
+        .map(|e| e.expand_as_named_arg())
+        .collect();
+
+    let props_ident = Ident::new(&format!("{}{}", ident, PROPS_SUFFIX), ident.span());
+    let event_ident = Ident::new(&format!("{}{}", ident, EVENT_SUFFIX), ident.span());
+    let span = ident.span();
+    // The spread props fill in the remaining fields of the props.
+    let props = match spread {
+        Some(spread) => {
+            let spread = &spread.value;
+            quote_spanned! {span=>
+                #props_ident!(..#spread; #(#prop_attributes),*)
+            }
+        }
+        None => quote_spanned! {span=>
+            #props_ident!(#(#prop_attributes),*)
+        },
+    };
+    quote_spanned!{span=>
+        ruukh::vdom::vcomponent::VComponent::new::<#ident #generics>(
+            #props,
+            #event_ident!(#(#event_attributes),*),
+        )
+    }
+}
 
-                let props_ident = Ident::new(&format!("{}{}", ident, PROPS_SUFFIX), ident.span());
-                let event_ident = Ident::new(&format!("{}{}", ident, EVENT_SUFFIX), ident.span());
-                let span = ident.span();
-                quote_spanned!{span=>
-                    ruukh::vdom::vcomponent::VComponent::new::<#ident>(
-                        #props_ident!(#(#prop_attributes),*),
-                        #event_ident!(#(#event_attributes),*),
-                    )
-                }
+impl ClosingTag {
+    /// Whether it closes a void element, which never has a closing tag.
+    pub fn is_void(&self) -> bool {
+        match self.tag_name {
+            TagName::Tag { ref name, .. } => {
+                spec::element(name).is_some_and(|el| el.content == Content::Void)
             }
+            TagName::Component { .. } => false,
         }
     }
 }
@@ -235,6 +306,7 @@ pub struct SelfClosingTag {
     pub lt: Token![<],
     pub tag_name: TagName,
     pub key: Option<KeyAttribute>,
+    pub spread: Option<SpreadAttribute>,
     pub prop_attributes: Vec<HtmlAttribute>,
     pub event_attributes: Vec<HtmlAttribute>,
     pub slash: Option<Token![/]>,
@@ -244,13 +316,19 @@ pub struct SelfClosingTag {
 impl Parse for SelfClosingTag {
     fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
         let lt = input.parse()?;
-        let tag_name = input.parse()?;
+        let tag_name: TagName = input.parse()?;
         let mut key = None;
+        let mut spread = None;
 
         let mut attributes: Vec<HtmlAttribute> = vec![];
         while !input.peek(Token![/]) && !input.peek(Token![>]) {
             if input.peek(kw::key) {
                 key = Some(input.parse()?);
+            } else if input.peek(Token![..]) {
+                if spread.is_some() {
+                    return Err(input.error("only a single spread is allowed on a tag."));
+                }
+                spread = Some(input.parse()?);
             } else {
                 attributes.push(input.parse()?);
             }
@@ -259,13 +337,21 @@ impl Parse for SelfClosingTag {
         let slash = input.parse()?;
         let gt = input.parse()?;
 
-        let (prop_attributes, event_attributes) =
-            attributes.into_iter().partition(|attr| attr.at.is_none());
+        let (mut prop_attributes, mut event_attributes) = attributes
+            .into_iter()
+            .partition::<Vec<_>, _>(|attr| attr.at.is_none());
+
+        // The props of a component are passed in order, as in an opening tag.
+        if tag_name.is_component() {
+            prop_attributes.sort_by(|l, r| l.key.name.cmp(&r.key.name));
+            event_attributes.sort_by(|l, r| l.key.name.cmp(&r.key.name));
+        }
 
         Ok(SelfClosingTag {
             lt,
             tag_name,
             key,
+            spread,
             prop_attributes,
             event_attributes,
             slash,
@@ -278,11 +364,8 @@ impl SelfClosingTag {
     fn expand(&self) -> TokenStream {
         match self.tag_name {
             TagName::Tag { ref name, .. } => {
-                let prop_attributes: Vec<_> = self
-                    .prop_attributes
-                    .iter()
-                    .map(|p| p.expand_as_prop_attribute().unwrap())
-                    .collect();
+                let prop_attributes =
+                    expand_element_attributes(&self.prop_attributes, self.spread.as_ref());
                 let event_attributes: Vec<_> = self
                     .event_attributes
                     .iter()
@@ -292,12 +375,21 @@ impl SelfClosingTag {
                 quote! {
                     ruukh::vdom::velement::VElement::childless(
                         #name,
-                        vec![#(#prop_attributes),*],
+                        #prop_attributes,
                         vec![#(#event_attributes),*]
                     )
                 }
             }
-            _ => unreachable!("The spec specified self-closing tags are the only ones allowed."),
+            TagName::Component {
+                ref ident,
+                ref generics,
+            } => expand_component(
+                ident,
+                generics.as_ref(),
+                self.spread.as_ref(),
+                &self.prop_attributes,
+                &self.event_attributes,
+            ),
         }
     }
 }
@@ -334,29 +426,128 @@ impl KeyAttribute {
     }
 }
 
+/// Expands the attributes of an element, placing the spread ones first so that
+/// the explicitly specified ones take precedence.
+fn expand_element_attributes(
+    attributes: &[HtmlAttribute],
+    spread: Option<&SpreadAttribute>,
+) -> TokenStream {
+    let attributes: Vec<_> = attributes
+        .iter()
+        .map(|p| p.expand_as_prop_attribute().unwrap())
+        .collect();
+
+    match spread {
+        Some(spread) => {
+            let spread = &spread.value;
+            quote! {
+                {
+                    let mut attributes =
+                        ruukh::vdom::velement::AttributeMap::to_attributes(&#spread);
+                    #(attributes.push(#attributes);)*
+                    attributes
+                }
+            }
+        }
+        None => quote! {
+            vec![#(#attributes),*]
+        },
+    }
+}
+
+/// `..expr` which spreads an `AttributeMap` onto an element or the props onto
+/// a component.
+///
+/// The expression is either a path like `self.attrs` or `props.get()`, or any
+/// expression within braces.
+pub struct SpreadAttribute {
+    pub value: Expr,
+}
+
+impl Parse for SpreadAttribute {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        input.parse::<Token![..]>()?;
+        if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            return Ok(SpreadAttribute {
+                value: content.parse()?,
+            });
+        }
+
+        // Parse the tokens manually as `>` would be mistaken for an operator.
+        let mut tokens = TokenStream::new();
+        loop {
+            let ident = input.call(Ident::parse_any)?;
+            tokens.extend(quote!(#ident));
+            if input.peek(token::Paren) {
+                let args: TokenTree = input.parse()?;
+                tokens.extend(Some(args));
+            }
+
+            if input.peek(Token![.]) {
+                let dot: Token![.] = input.parse()?;
+                tokens.extend(quote!(#dot));
+            } else if input.peek(Token![::]) {
+                let colons: Token![::] = input.parse()?;
+                tokens.extend(quote!(#colons));
+            } else {
+                break;
+            }
+        }
+
+        Ok(SpreadAttribute {
+            value: syn::parse2(tokens)?,
+        })
+    }
+}
+
 pub struct HtmlAttribute {
     pub at: Option<Token![@]>,
     pub key: AttributeName,
-    pub eq: Token![=],
-    pub brace: token::Brace,
     pub value: Expr,
 }
 
 impl Parse for HtmlAttribute {
     fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
-        let at = input.parse()?;
-        let key = input.parse()?;
-        let eq = input.parse()?;
-        let content;
-        let brace = braced!(content in input);
-        let value = content.parse()?;
-        Ok(HtmlAttribute {
-            at,
-            key,
-            eq,
-            brace,
-            value,
-        })
+        let at: Option<Token![@]> = input.parse()?;
+
+        // Field shorthand, i.e. `{class}` is the same as `class={class}`.
+        if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            let ident: Ident = content.parse()?;
+            return Ok(HtmlAttribute {
+                at,
+                key: AttributeName {
+                    name: ident.to_string().to_kebab_case(),
+                    span: ident.span(),
+                },
+                value: parse_quote!(#ident),
+            });
+        }
+
+        let key: AttributeName = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            if input.peek(Lit) {
+                Expr::Lit(ExprLit {
+                    attrs: vec![],
+                    lit: input.parse()?,
+                })
+            } else {
+                let content;
+                braced!(content in input);
+                content.parse()?
+            }
+        } else if at.is_some() {
+            return Err(input.error(format!("expected a listener for `@{}`.", key.name)));
+        } else {
+            // A valueless attribute is a boolean one which is set.
+            parse_quote!(true)
+        };
+
+        Ok(HtmlAttribute { at, key, value })
     }
 }
 
@@ -394,8 +585,16 @@ impl HtmlAttribute {
 }
 
 pub enum TagName {
-    Tag { name: String, span: Span },
-    Component { ident: Ident },
+    Tag {
+        name: String,
+        span: Span,
+    },
+    /// A component along with its generic arguments like `List<User>`, which
+    /// may be omitted on the closing tag.
+    Component {
+        ident: Ident,
+        generics: Option<AngleBracketedGenericArguments>,
+    },
 }
 
 impl TagName {
@@ -406,10 +605,10 @@ impl TagName {
         }
     }
 
-    fn span(&self) -> Span {
+    pub fn span(&self) -> Span {
         match self {
             TagName::Tag { ref span, .. } => span.clone(),
-            TagName::Component { ref ident } => ident.span(),
+            TagName::Component { ref ident, .. } => ident.span(),
         }
     }
 }
@@ -425,8 +624,14 @@ impl Parse for TagName {
             if idents.len() != 1 {
                 return Err(Error::new(span, "no dashes in a component tag allowed."));
             }
+            let generics = if input.peek(Token![<]) {
+                Some(input.parse()?)
+            } else {
+                None
+            };
             return Ok(TagName::Component {
                 ident: idents.swap_remove(0),
+                generics,
             });
         }
 
@@ -452,12 +657,15 @@ impl Parse for TagName {
 }
 
 pub struct AttributeName {
-    name: String,
+    pub name: String,
+    pub span: Span,
 }
 
 impl Parse for AttributeName {
     fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
-        let idents = input.call(Punctuated::<Ident, Token![-]>::parse_separated_nonempty)?;
+        // Keywords are allowed for attributes like `for` & `type`.
+        let idents =
+            Punctuated::<Ident, Token![-]>::parse_separated_nonempty_with(input, Ident::parse_any)?;
         let span = idents.span();
         let name = idents
             .into_iter()
@@ -473,7 +681,7 @@ impl Parse for AttributeName {
             ));
         }
 
-        Ok(AttributeName { name })
+        Ok(AttributeName { name, span })
     }
 }
 
@@ -521,12 +729,75 @@ mod test {
         let _: SelfClosingTag = syn::parse_str("<input/>").unwrap();
     }
 
+    #[test]
+    fn should_parse_self_closing_component() {
+        let parsed: HtmlElement = syn::parse_str("<Badge label={label} color={color} />").unwrap();
+        match parsed {
+            HtmlElement::SelfClosing(el) => {
+                assert!(el.tag.tag_name.is_component());
+                assert_eq!(el.tag.prop_attributes[0].key.name, "color");
+            }
+            _ => panic!("expected a self-closing component"),
+        }
+
+        let parsed: HtmlElement = syn::parse_str("<Badge label={label}></Badge>").unwrap();
+        match parsed {
+            HtmlElement::Normal(_) => {}
+            _ => panic!("expected a normal component"),
+        }
+    }
+
     #[test]
     fn should_parse_normal_attribute() {
         let attr: HtmlAttribute = syn::parse_str(r#"name={"value"}"#).unwrap();
         assert!(attr.at.is_none());
     }
 
+    #[test]
+    fn should_parse_literal_attribute() {
+        let attr: HtmlAttribute = syn::parse_str(r#"name="value""#).unwrap();
+        assert_eq!(attr.key.name, "name");
+        assert!(matches!(attr.value, Expr::Lit(_)));
+    }
+
+    #[test]
+    fn should_parse_valueless_attribute() {
+        let tag: SelfClosingTag = syn::parse_str("<input disabled>").unwrap();
+        assert_eq!(tag.prop_attributes.len(), 1);
+        assert_eq!(tag.prop_attributes[0].key.name, "disabled");
+    }
+
+    #[test]
+    fn should_parse_shorthand_attribute() {
+        let attr: HtmlAttribute = syn::parse_str("{aria_label}").unwrap();
+        assert_eq!(attr.key.name, "aria-label");
+        assert!(attr.at.is_none());
+    }
+
+    #[test]
+    fn should_not_parse_valueless_event_attribute() {
+        let res: ParseResult<OpeningTag> = syn::parse_str("<button @click>");
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn should_parse_spread_attribute() {
+        let tag: OpeningTag = syn::parse_str("<button ..self.attrs class=\"btn\">").unwrap();
+        assert!(tag.spread.is_some());
+        assert_eq!(tag.prop_attributes.len(), 1);
+
+        let tag: SelfClosingTag = syn::parse_str("<input ..self.attrs()/>").unwrap();
+        assert!(tag.spread.is_some());
+
+        let _: OpeningTag = syn::parse_str("<Child ..{ props(1) }>").unwrap();
+    }
+
+    #[test]
+    fn should_not_parse_multiple_spreads() {
+        let res: ParseResult<OpeningTag> = syn::parse_str("<div ..a ..b>");
+        assert!(res.is_err());
+    }
+
     #[test]
     fn should_parse_event_attribute() {
         let attr: HtmlAttribute = syn::parse_str(r#"@input={fn_name}"#).unwrap();
@@ -536,15 +807,30 @@ mod test {
     #[test]
     fn should_parse_single_tag_name() {
         let parsed: TagName = syn::parse_str("Identifier").unwrap();
-        if let TagName::Component { ident } = parsed {
+        if let TagName::Component { ident, .. } = parsed {
             assert_eq!(ident, "Identifier");
         }
     }
 
+    #[test]
+    fn should_parse_generic_component_tag_name() {
+        let parsed: TagName = syn::parse_str("List<User>").unwrap();
+        match parsed {
+            TagName::Component {
+                ident,
+                generics: Some(_),
+            } => assert_eq!(ident, "List"),
+            _ => panic!("expected a generic component"),
+        }
+
+        let tag: OpeningTag = syn::parse_str("<List<Vec<User>> items={items}>").unwrap();
+        assert_eq!(tag.prop_attributes.len(), 1);
+    }
+
     #[test]
     fn should_parse_dashed_tag_name() {
         let parsed: TagName = syn::parse_str("first-second-third").unwrap();
-        if let TagName::Component { ident } = parsed {
+        if let TagName::Component { ident, .. } = parsed {
             assert_eq!(ident, "first-second-third");
         }
     }
@@ -0,0 +1,757 @@
+//! A table of the elements in the HTML specification, used to validate the
+//! markup at compile time.
+//!
+//! It covers the content categories, the content model, the element specific
+//! attributes & the elements which are disallowed as descendants of an element.
+//! It is deliberately lenient where the specification depends upon the
+//! attributes, except for their literal values, or the runtime values.
+use self::Content::*;
+
+/// The content categories an element belongs to.
+pub type Categories = u8;
+
+pub const NO_CATEGORY: Categories = 0;
+pub const METADATA: Categories = 1;
+pub const FLOW: Categories = 1 << 1;
+pub const PHRASING: Categories = 1 << 2;
+pub const INTERACTIVE: Categories = 1 << 3;
+
+/// What an element may contain as its children.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Content {
+    /// A void element which has no children or closing tag.
+    Void,
+    /// No children at all, though it has a closing tag.
+    Nothing,
+    /// Only text.
+    Text,
+    /// Flow content and the given elements.
+    Flow(&'static [&'static str]),
+    /// Phrasing content and the given elements.
+    Phrasing(&'static [&'static str]),
+    /// Whatever its parent allows and the given elements.
+    Transparent(&'static [&'static str]),
+    /// Only the given elements.
+    Only(&'static [&'static str]),
+    /// Anything at all.
+    Any,
+    /// Content of another namespace like SVG & MathML, which is not validated.
+    Foreign,
+}
+
+impl Content {
+    /// Whether the element may be a child. Needs to be resolved for a
+    /// transparent content beforehand.
+    pub fn allows(self, child: &ElementSpec) -> bool {
+        match self {
+            Void | Nothing | Text => false,
+            Flow(extras) => child.categories & FLOW != 0 || extras.contains(&child.name),
+            Phrasing(extras) => child.categories & PHRASING != 0 || extras.contains(&child.name),
+            Transparent(extras) | Only(extras) => extras.contains(&child.name),
+            Any | Foreign => true,
+        }
+    }
+
+    /// Whether text may be a child.
+    pub fn allows_text(self) -> bool {
+        !matches!(self, Void | Nothing | Only(_))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ElementSpec {
+    pub name: &'static str,
+    pub categories: Categories,
+    pub content: Content,
+    attributes: &'static [&'static str],
+    forbidden_categories: Categories,
+    forbidden_tags: &'static [&'static str],
+}
+
+impl ElementSpec {
+    const fn attributes(self, attributes: &'static [&'static str]) -> ElementSpec {
+        ElementSpec { attributes, ..self }
+    }
+
+    const fn forbids(
+        self,
+        forbidden_categories: Categories,
+        forbidden_tags: &'static [&'static str],
+    ) -> ElementSpec {
+        ElementSpec {
+            forbidden_categories,
+            forbidden_tags,
+            ..self
+        }
+    }
+
+    /// Whether the attribute is allowed on the element.
+    pub fn allows_attribute(&self, attribute: &str) -> bool {
+        self.content == Foreign
+            || self.attributes.contains(&attribute)
+            || GLOBAL_ATTRIBUTES.contains(&attribute)
+            || attribute.starts_with("data-")
+            || attribute.starts_with("aria-")
+            || EVENT_HANDLER_ATTRIBUTES.contains(&attribute)
+    }
+
+    /// The spec of the element given the `literal` string values of its
+    /// attributes. An `<input type="hidden">` is not interactive content.
+    pub fn resolve(self, literal: impl Fn(&str) -> Option<String>) -> ElementSpec {
+        let is_hidden_input = self.name == "input"
+            && literal("type").is_some_and(|type_| type_.eq_ignore_ascii_case("hidden"));
+        if is_hidden_input {
+            ElementSpec {
+                categories: self.categories & !INTERACTIVE,
+                ..self
+            }
+        } else {
+            self
+        }
+    }
+
+    /// Whether the element is disallowed anywhere within this element.
+    pub fn forbids_descendant(&self, descendant: &ElementSpec) -> bool {
+        descendant.categories & self.forbidden_categories != 0
+            || self.forbidden_tags.contains(&descendant.name)
+    }
+}
+
+const fn el(name: &'static str, categories: Categories, content: Content) -> ElementSpec {
+    ElementSpec {
+        name,
+        categories,
+        content,
+        attributes: &[],
+        forbidden_categories: NO_CATEGORY,
+        forbidden_tags: &[],
+    }
+}
+
+/// The spec of a custom element, which may be placed anywhere & contain
+/// anything.
+pub const CUSTOM_ELEMENT: ElementSpec = el("", FLOW | PHRASING, Any);
+
+/// The spec of an SVG element at the root of a markup, which is to be placed
+/// within an `<svg>` elsewhere.
+pub const SVG_FRAGMENT: ElementSpec = el("", FLOW | PHRASING, Foreign);
+
+/// Whether the tag is of an element within an `<svg>`.
+pub fn is_svg_element(tag: &str) -> bool {
+    SVG_ELEMENTS.contains(&tag)
+}
+
+/// Finds the spec of the element with the given tag.
+pub fn element(tag: &str) -> Option<&'static ElementSpec> {
+    ELEMENTS.iter().find(|el| el.name == tag)
+}
+
+const GLOBAL_ATTRIBUTES: &[&str] = &[
+    "accesskey",
+    "autocapitalize",
+    "autofocus",
+    "class",
+    "contenteditable",
+    "dir",
+    "draggable",
+    "enterkeyhint",
+    "hidden",
+    "id",
+    "inert",
+    "inputmode",
+    "is",
+    "itemid",
+    "itemprop",
+    "itemref",
+    "itemscope",
+    "itemtype",
+    "lang",
+    "nonce",
+    "part",
+    "popover",
+    "role",
+    "slot",
+    "spellcheck",
+    "style",
+    "tabindex",
+    "title",
+    "translate",
+];
+
+const EVENT_HANDLER_ATTRIBUTES: &[&str] = &[
+    "onabort",
+    "onafterprint",
+    "onauxclick",
+    "onbeforeinput",
+    "onbeforematch",
+    "onbeforeprint",
+    "onbeforetoggle",
+    "onbeforeunload",
+    "onblur",
+    "oncancel",
+    "oncanplay",
+    "oncanplaythrough",
+    "onchange",
+    "onclick",
+    "onclose",
+    "oncontextlost",
+    "oncontextmenu",
+    "oncontextrestored",
+    "oncopy",
+    "oncuechange",
+    "oncut",
+    "ondblclick",
+    "ondrag",
+    "ondragend",
+    "ondragenter",
+    "ondragleave",
+    "ondragover",
+    "ondragstart",
+    "ondrop",
+    "ondurationchange",
+    "onemptied",
+    "onended",
+    "onerror",
+    "onfocus",
+    "onformdata",
+    "onhashchange",
+    "oninput",
+    "oninvalid",
+    "onkeydown",
+    "onkeypress",
+    "onkeyup",
+    "onlanguagechange",
+    "onload",
+    "onloadeddata",
+    "onloadedmetadata",
+    "onloadstart",
+    "onmessage",
+    "onmessageerror",
+    "onmousedown",
+    "onmouseenter",
+    "onmouseleave",
+    "onmousemove",
+    "onmouseout",
+    "onmouseover",
+    "onmouseup",
+    "onoffline",
+    "ononline",
+    "onpagehide",
+    "onpagereveal",
+    "onpageshow",
+    "onpageswap",
+    "onpaste",
+    "onpause",
+    "onplay",
+    "onplaying",
+    "onpopstate",
+    "onprogress",
+    "onratechange",
+    "onrejectionhandled",
+    "onreset",
+    "onresize",
+    "onscroll",
+    "onscrollend",
+    "onsecuritypolicyviolation",
+    "onseeked",
+    "onseeking",
+    "onselect",
+    "onslotchange",
+    "onstalled",
+    "onstorage",
+    "onsubmit",
+    "onsuspend",
+    "ontimeupdate",
+    "ontoggle",
+    "onunhandledrejection",
+    "onunload",
+    "onvolumechange",
+    "onwaiting",
+    "onwheel",
+];
+
+const SVG_ELEMENTS: &[&str] = &[
+    "animate",
+    "animateMotion",
+    "animateTransform",
+    "circle",
+    "clipPath",
+    "defs",
+    "desc",
+    "ellipse",
+    "feBlend",
+    "feColorMatrix",
+    "feComponentTransfer",
+    "feComposite",
+    "feConvolveMatrix",
+    "feDiffuseLighting",
+    "feDisplacementMap",
+    "feDistantLight",
+    "feDropShadow",
+    "feFlood",
+    "feFuncA",
+    "feFuncB",
+    "feFuncG",
+    "feFuncR",
+    "feGaussianBlur",
+    "feImage",
+    "feMerge",
+    "feMergeNode",
+    "feMorphology",
+    "feOffset",
+    "fePointLight",
+    "feSpecularLighting",
+    "feSpotLight",
+    "feTile",
+    "feTurbulence",
+    "filter",
+    "foreignObject",
+    "g",
+    "image",
+    "line",
+    "linearGradient",
+    "marker",
+    "mask",
+    "metadata",
+    "mpath",
+    "path",
+    "pattern",
+    "polygon",
+    "polyline",
+    "radialGradient",
+    "rect",
+    "set",
+    "stop",
+    "switch",
+    "symbol",
+    "text",
+    "textPath",
+    "tspan",
+    "use",
+    "view",
+];
+
+const SECTIONING_AND_HEADINGS: &[&str] = &[
+    "address", "article", "aside", "footer", "h1", "h2", "h3", "h4", "h5", "h6", "header",
+    "hgroup", "nav", "section",
+];
+const MEDIA_ATTRIBUTES: &[&str] = &[
+    "autoplay",
+    "controls",
+    "crossorigin",
+    "height",
+    "loop",
+    "muted",
+    "playsinline",
+    "poster",
+    "preload",
+    "src",
+    "width",
+];
+const FORM_SUBMIT_ATTRIBUTES: &[&str] = &[
+    "disabled",
+    "form",
+    "formaction",
+    "formenctype",
+    "formmethod",
+    "formnovalidate",
+    "formtarget",
+    "name",
+    "popovertarget",
+    "popovertargetaction",
+    "type",
+    "value",
+];
+const CELL_ATTRIBUTES: &[&str] = &["abbr", "colspan", "headers", "rowspan", "scope"];
+const TABLE_SECTION: Content = Only(&["script", "template", "tr"]);
+const LIST: Content = Only(&["li", "script", "template"]);
+const TEXT_LEVEL: Content = Phrasing(&[]);
+
+static ELEMENTS: &[ElementSpec] = &[
+    // The document and metadata.
+    el("html", NO_CATEGORY, Only(&["body", "head"])).attributes(&["manifest", "xmlns"]),
+    el(
+        "head",
+        NO_CATEGORY,
+        Only(&[
+            "base", "link", "meta", "noscript", "script", "style", "template", "title",
+        ]),
+    ),
+    el("title", METADATA, Text),
+    el("base", METADATA, Void).attributes(&["href", "target"]),
+    el("link", METADATA | FLOW | PHRASING, Void).attributes(&[
+        "as",
+        "blocking",
+        "color",
+        "crossorigin",
+        "disabled",
+        "fetchpriority",
+        "href",
+        "hreflang",
+        "imagesizes",
+        "imagesrcset",
+        "integrity",
+        "media",
+        "referrerpolicy",
+        "rel",
+        "sizes",
+        "type",
+    ]),
+    el("meta", METADATA | FLOW | PHRASING, Void).attributes(&[
+        "charset",
+        "content",
+        "http-equiv",
+        "media",
+        "name",
+    ]),
+    el("style", METADATA | FLOW, Text).attributes(&["blocking", "media"]),
+    el("body", NO_CATEGORY, Flow(&[])),
+    // Sections.
+    el("article", FLOW, Flow(&[])),
+    el("section", FLOW, Flow(&[])),
+    el("nav", FLOW, Flow(&[])),
+    el("aside", FLOW, Flow(&[])),
+    el("h1", FLOW, TEXT_LEVEL),
+    el("h2", FLOW, TEXT_LEVEL),
+    el("h3", FLOW, TEXT_LEVEL),
+    el("h4", FLOW, TEXT_LEVEL),
+    el("h5", FLOW, TEXT_LEVEL),
+    el("h6", FLOW, TEXT_LEVEL),
+    el(
+        "hgroup",
+        FLOW,
+        Only(&["h1", "h2", "h3", "h4", "h5", "h6", "p", "script", "template"]),
+    ),
+    el("header", FLOW, Flow(&[])).forbids(NO_CATEGORY, &["footer", "header"]),
+    el("footer", FLOW, Flow(&[])).forbids(NO_CATEGORY, &["footer", "header"]),
+    el("address", FLOW, Flow(&[])).forbids(NO_CATEGORY, SECTIONING_AND_HEADINGS),
+    el("main", FLOW, Flow(&[])),
+    el("search", FLOW, Flow(&[])),
+    // Grouping content.
+    el("p", FLOW, TEXT_LEVEL),
+    el("hr", FLOW, Void),
+    el("pre", FLOW, TEXT_LEVEL),
+    el("blockquote", FLOW, Flow(&[])).attributes(&["cite"]),
+    el("ol", FLOW, LIST).attributes(&["reversed", "start", "type"]),
+    el("ul", FLOW, LIST),
+    el("menu", FLOW, LIST),
+    el("li", NO_CATEGORY, Flow(&[])).attributes(&["value"]),
+    el(
+        "dl",
+        FLOW,
+        Only(&["dd", "div", "dt", "script", "template"]),
+    ),
+    el("dt", NO_CATEGORY, Flow(&[])).forbids(NO_CATEGORY, SECTIONING_AND_HEADINGS),
+    el("dd", NO_CATEGORY, Flow(&[])),
+    el("figure", FLOW, Flow(&["figcaption"])),
+    el("figcaption", NO_CATEGORY, Flow(&[])),
+    // A `div` may group the `dt` & `dd` of a `dl`.
+    el("div", FLOW, Flow(&["dd", "dt"])),
+    // Text-level semantics.
+    el("a", FLOW | PHRASING | INTERACTIVE, Transparent(&[]))
+        .attributes(&[
+            "download",
+            "href",
+            "hreflang",
+            "ping",
+            "referrerpolicy",
+            "rel",
+            "target",
+            "type",
+        ]).forbids(INTERACTIVE, &[]),
+    el("em", FLOW | PHRASING, TEXT_LEVEL),
+    el("strong", FLOW | PHRASING, TEXT_LEVEL),
+    el("small", FLOW | PHRASING, TEXT_LEVEL),
+    el("s", FLOW | PHRASING, TEXT_LEVEL),
+    el("cite", FLOW | PHRASING, TEXT_LEVEL),
+    el("q", FLOW | PHRASING, TEXT_LEVEL).attributes(&["cite"]),
+    el("dfn", FLOW | PHRASING, TEXT_LEVEL).forbids(NO_CATEGORY, &["dfn"]),
+    el("abbr", FLOW | PHRASING, TEXT_LEVEL),
+    el("ruby", FLOW | PHRASING, Phrasing(&["rp", "rt"])),
+    el("rt", NO_CATEGORY, TEXT_LEVEL),
+    el("rp", NO_CATEGORY, Text),
+    el("data", FLOW | PHRASING, TEXT_LEVEL).attributes(&["value"]),
+    el("time", FLOW | PHRASING, TEXT_LEVEL).attributes(&["datetime"]),
+    el("code", FLOW | PHRASING, TEXT_LEVEL),
+    el("var", FLOW | PHRASING, TEXT_LEVEL),
+    el("samp", FLOW | PHRASING, TEXT_LEVEL),
+    el("kbd", FLOW | PHRASING, TEXT_LEVEL),
+    el("sub", FLOW | PHRASING, TEXT_LEVEL),
+    el("sup", FLOW | PHRASING, TEXT_LEVEL),
+    el("i", FLOW | PHRASING, TEXT_LEVEL),
+    el("b", FLOW | PHRASING, TEXT_LEVEL),
+    el("u", FLOW | PHRASING, TEXT_LEVEL),
+    el("mark", FLOW | PHRASING, TEXT_LEVEL),
+    el("bdi", FLOW | PHRASING, TEXT_LEVEL),
+    el("bdo", FLOW | PHRASING, TEXT_LEVEL),
+    el("span", FLOW | PHRASING, TEXT_LEVEL),
+    el("br", FLOW | PHRASING, Void),
+    el("wbr", FLOW | PHRASING, Void),
+    // Edits.
+    el("ins", FLOW | PHRASING, Transparent(&[])).attributes(&["cite", "datetime"]),
+    el("del", FLOW | PHRASING, Transparent(&[])).attributes(&["cite", "datetime"]),
+    // Embedded content.
+    el(
+        "picture",
+        FLOW | PHRASING,
+        Only(&["img", "script", "source", "template"]),
+    ),
+    el("source", NO_CATEGORY, Void).attributes(&[
+        "height", "media", "sizes", "src", "srcset", "type", "width",
+    ]),
+    el("img", FLOW | PHRASING, Void).attributes(&[
+        "alt",
+        "crossorigin",
+        "decoding",
+        "fetchpriority",
+        "height",
+        "ismap",
+        "loading",
+        "referrerpolicy",
+        "sizes",
+        "src",
+        "srcset",
+        "usemap",
+        "width",
+    ]),
+    el("iframe", FLOW | PHRASING | INTERACTIVE, Nothing).attributes(&[
+        "allow",
+        "allowfullscreen",
+        "height",
+        "loading",
+        "name",
+        "referrerpolicy",
+        "sandbox",
+        "src",
+        "srcdoc",
+        "width",
+    ]),
+    el("embed", FLOW | PHRASING | INTERACTIVE, Void).attributes(&["height", "src", "type", "width"]),
+    el("object", FLOW | PHRASING, Transparent(&["param"])).attributes(&[
+        "data", "form", "height", "name", "type", "width",
+    ]),
+    el("param", NO_CATEGORY, Void).attributes(&["name", "value"]),
+    el("video", FLOW | PHRASING, Transparent(&["source", "track"])).attributes(MEDIA_ATTRIBUTES),
+    el("audio", FLOW | PHRASING, Transparent(&["source", "track"])).attributes(MEDIA_ATTRIBUTES),
+    el("track", NO_CATEGORY, Void).attributes(&["default", "kind", "label", "src", "srclang"]),
+    el("map", FLOW | PHRASING, Transparent(&[])).attributes(&["name"]),
+    el("area", FLOW | PHRASING, Void).attributes(&[
+        "alt",
+        "coords",
+        "download",
+        "href",
+        "ping",
+        "referrerpolicy",
+        "rel",
+        "shape",
+        "target",
+    ]),
+    el("svg", FLOW | PHRASING, Foreign),
+    el("math", FLOW | PHRASING, Foreign),
+    el("canvas", FLOW | PHRASING, Transparent(&[])).attributes(&["height", "width"]),
+    // Tabular data.
+    el(
+        "table",
+        FLOW,
+        Only(&[
+            "caption", "colgroup", "script", "tbody", "template", "tfoot", "thead", "tr",
+        ]),
+    ),
+    el("caption", NO_CATEGORY, Flow(&[])).forbids(NO_CATEGORY, &["table"]),
+    el("colgroup", NO_CATEGORY, Only(&["col", "template"])).attributes(&["span"]),
+    el("col", NO_CATEGORY, Void).attributes(&["span"]),
+    el("tbody", NO_CATEGORY, TABLE_SECTION),
+    el("thead", NO_CATEGORY, TABLE_SECTION),
+    el("tfoot", NO_CATEGORY, TABLE_SECTION),
+    el("tr", NO_CATEGORY, Only(&["script", "td", "template", "th"])),
+    el("td", NO_CATEGORY, Flow(&[])).attributes(CELL_ATTRIBUTES),
+    el("th", NO_CATEGORY, Flow(&[])).attributes(CELL_ATTRIBUTES),
+    // Forms.
+    el("form", FLOW, Flow(&[]))
+        .attributes(&[
+            "accept-charset",
+            "action",
+            "autocomplete",
+            "enctype",
+            "method",
+            "name",
+            "novalidate",
+            "rel",
+            "target",
+        ]).forbids(NO_CATEGORY, &["form"]),
+    el("label", FLOW | PHRASING | INTERACTIVE, TEXT_LEVEL)
+        .attributes(&["for"])
+        .forbids(NO_CATEGORY, &["label"]),
+    el("input", FLOW | PHRASING | INTERACTIVE, Void).attributes(&[
+        "accept",
+        "alt",
+        "autocomplete",
+        "capture",
+        "checked",
+        "dirname",
+        "disabled",
+        "form",
+        "formaction",
+        "formenctype",
+        "formmethod",
+        "formnovalidate",
+        "formtarget",
+        "height",
+        "list",
+        "max",
+        "maxlength",
+        "min",
+        "minlength",
+        "multiple",
+        "name",
+        "pattern",
+        "placeholder",
+        "popovertarget",
+        "popovertargetaction",
+        "readonly",
+        "required",
+        "size",
+        "src",
+        "step",
+        "type",
+        "value",
+        "width",
+    ]),
+    el("button", FLOW | PHRASING | INTERACTIVE, TEXT_LEVEL)
+        .attributes(FORM_SUBMIT_ATTRIBUTES)
+        .forbids(INTERACTIVE, &[]),
+    el(
+        "select",
+        FLOW | PHRASING | INTERACTIVE,
+        Only(&["hr", "optgroup", "option", "script", "template"]),
+    ).attributes(&[
+        "autocomplete",
+        "disabled",
+        "form",
+        "multiple",
+        "name",
+        "required",
+        "size",
+    ]),
+    el("datalist", FLOW | PHRASING, Phrasing(&["option"])),
+    el(
+        "optgroup",
+        NO_CATEGORY,
+        Only(&["option", "script", "template"]),
+    ).attributes(&["disabled", "label"]),
+    el("option", NO_CATEGORY, Text).attributes(&["disabled", "label", "selected", "value"]),
+    el("textarea", FLOW | PHRASING | INTERACTIVE, Text).attributes(&[
+        "autocomplete",
+        "cols",
+        "dirname",
+        "disabled",
+        "form",
+        "maxlength",
+        "minlength",
+        "name",
+        "placeholder",
+        "readonly",
+        "required",
+        "rows",
+        "wrap",
+    ]),
+    el("output", FLOW | PHRASING, TEXT_LEVEL).attributes(&["for", "form", "name"]),
+    el("progress", FLOW | PHRASING, TEXT_LEVEL)
+        .attributes(&["max", "value"])
+        .forbids(NO_CATEGORY, &["progress"]),
+    el("meter", FLOW | PHRASING, TEXT_LEVEL)
+        .attributes(&["high", "low", "max", "min", "optimum", "value"])
+        .forbids(NO_CATEGORY, &["meter"]),
+    el("fieldset", FLOW, Flow(&["legend"])).attributes(&["disabled", "form", "name"]),
+    el(
+        "legend",
+        NO_CATEGORY,
+        Phrasing(&["h1", "h2", "h3", "h4", "h5", "h6"]),
+    ),
+    // Interactive elements.
+    el("details", FLOW | INTERACTIVE, Flow(&["summary"])).attributes(&["name", "open"]),
+    el(
+        "summary",
+        NO_CATEGORY,
+        Phrasing(&["h1", "h2", "h3", "h4", "h5", "h6", "hgroup"]),
+    ),
+    el("dialog", FLOW, Flow(&[])).attributes(&["open"]),
+    // Scripting.
+    el("script", METADATA | FLOW | PHRASING, Text).attributes(&[
+        "async",
+        "blocking",
+        "crossorigin",
+        "defer",
+        "fetchpriority",
+        "integrity",
+        "nomodule",
+        "referrerpolicy",
+        "src",
+        "type",
+    ]),
+    el("noscript", METADATA | FLOW | PHRASING, Transparent(&[])),
+    el("template", METADATA | FLOW | PHRASING, Any).attributes(&[
+        "shadowrootclonable",
+        "shadowrootdelegatesfocus",
+        "shadowrootmode",
+    ]),
+    el("slot", FLOW | PHRASING, Transparent(&[])).attributes(&["name"]),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_allow_known_event_handlers() {
+        let div = element("div").unwrap();
+        assert!(div.allows_attribute("onclick"));
+        assert!(!div.allows_attribute("onclik"));
+        assert!(!div.allows_attribute("one"));
+    }
+
+    #[test]
+    fn should_find_elements() {
+        assert!(element("div").is_some());
+        assert!(element("dvi").is_none());
+    }
+
+    #[test]
+    fn should_allow_content() {
+        let p = element("p").unwrap();
+        let div = element("div").unwrap();
+        let span = element("span").unwrap();
+        assert!(!p.content.allows(div));
+        assert!(p.content.allows(span));
+        assert!(div.content.allows(p));
+    }
+
+    #[test]
+    fn should_allow_attributes() {
+        let a = element("a").unwrap();
+        assert!(a.allows_attribute("href"));
+        assert!(a.allows_attribute("class"));
+        assert!(a.allows_attribute("data-id"));
+        assert!(a.allows_attribute("aria-label"));
+        assert!(!a.allows_attribute("hreff"));
+    }
+
+    #[test]
+    fn should_forbid_nested_interactive_elements() {
+        let a = element("a").unwrap();
+        assert!(a.forbids_descendant(a));
+        assert!(a.forbids_descendant(element("button").unwrap()));
+        assert!(!a.forbids_descendant(element("span").unwrap()));
+    }
+
+    #[test]
+    fn should_resolve_hidden_input_as_not_interactive() {
+        let a = element("a").unwrap();
+        let input = *element("input").unwrap();
+        let hidden = input.resolve(|_| Some("HIDDEN".to_string()));
+        assert!(!a.forbids_descendant(&hidden));
+        let text = input.resolve(|_| Some("text".to_string()));
+        assert!(a.forbids_descendant(&text));
+        assert!(a.forbids_descendant(&input.resolve(|_| None)));
+    }
+}
@@ -0,0 +1,272 @@
+//! Validation of the markup against the HTML specification.
+use super::{
+    control::{HtmlArmBody, HtmlElse, HtmlIf},
+    element::{HtmlAttribute, HtmlElement, TagName},
+    spec::{self, Content, ElementSpec, CUSTOM_ELEMENT, SVG_FRAGMENT},
+    HtmlItem, HtmlItems, HtmlRoot,
+};
+use proc_macro2::Span;
+use syn::{
+    parse::{Error, Result as ParseResult},
+    Expr, ExprLit, Lit,
+};
+
+/// The specs of the elements enclosing the markup, the innermost one last.
+type Ancestors = Vec<&'static ElementSpec>;
+
+impl HtmlRoot {
+    /// Validates the tags, attributes & the nesting of the elements.
+    /// Custom elements, i.e. tags with a dash, and components are exempt.
+    pub fn validate(&self) -> ParseResult<()> {
+        validate_root(self, &mut vec![])
+    }
+}
+
+fn validate_root(root: &HtmlRoot, ancestors: &mut Ancestors) -> ParseResult<()> {
+    for items in root.items.iter() {
+        let items = match items {
+            HtmlItems::Keyed(ref items) | HtmlItems::Unkeyed(ref items) => items,
+        };
+        for item in items {
+            validate_item(item, ancestors)?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_item(item: &HtmlItem, ancestors: &mut Ancestors) -> ParseResult<()> {
+    match item {
+        HtmlItem::Element(ref el) => validate_element(el, ancestors),
+        HtmlItem::Text(ref text) => {
+            if text.content.trim().is_empty() {
+                return Ok(());
+            }
+            match resolve_content(ancestors) {
+                Some((parent, content)) if !content.allows_text() => Err(Error::new(
+                    text.span,
+                    format!("text is not allowed within `<{}>`.", parent.name),
+                )),
+                _ => Ok(()),
+            }
+        }
+        HtmlItem::ExpressionBlock(_) => Ok(()),
+        HtmlItem::If(ref if_) => validate_if(if_, ancestors),
+        HtmlItem::For(ref for_) => validate_root(&for_.body, ancestors),
+        HtmlItem::Match(ref match_) => {
+            for arm in match_.arms.iter() {
+                match arm.body {
+                    HtmlArmBody::Block(ref root) => validate_root(root, ancestors)?,
+                    HtmlArmBody::Item(ref item) => validate_item(item, ancestors)?,
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+fn validate_if(if_: &HtmlIf, ancestors: &mut Ancestors) -> ParseResult<()> {
+    validate_root(&if_.then_branch, ancestors)?;
+    match if_.else_branch {
+        Some(HtmlElse::If(ref else_if)) => validate_if(else_if, ancestors),
+        Some(HtmlElse::Else(ref root)) => validate_root(root, ancestors),
+        None => Ok(()),
+    }
+}
+
+fn validate_element(el: &HtmlElement, ancestors: &mut Ancestors) -> ParseResult<()> {
+    let (tag_name, attributes, child) = el.parts();
+    let (name, span) = match tag_name {
+        TagName::Tag { ref name, ref span } => (name, *span),
+        TagName::Component { .. } => return Ok(()),
+    };
+
+    let spec = if name.contains('-') {
+        &CUSTOM_ELEMENT
+    } else if let Some(spec) = spec::element(name) {
+        validate_attributes(spec, attributes)?;
+        spec
+    } else if ancestors.is_empty() && spec::is_svg_element(name) {
+        // A markup of SVG elements to be placed within an `<svg>` elsewhere.
+        &SVG_FRAGMENT
+    } else {
+        return Err(Error::new(
+            span,
+            format!(
+                "unknown tag `<{}>`. Custom elements need a dash in their name.",
+                name
+            ),
+        ));
+    };
+
+    let placed = spec.resolve(|key| literal_attribute(attributes, key));
+    validate_placement(name, &placed, span, ancestors)?;
+
+    if let Some(child) = child {
+        if spec.content == Content::Foreign {
+            return Ok(());
+        }
+        ancestors.push(spec);
+        let validated = validate_root(child, ancestors);
+        ancestors.pop();
+        validated?;
+    }
+    Ok(())
+}
+
+fn validate_attributes(spec: &ElementSpec, attributes: &[HtmlAttribute]) -> ParseResult<()> {
+    for attr in attributes {
+        if !spec.allows_attribute(&attr.key.name) {
+            return Err(Error::new(
+                attr.key.span,
+                format!(
+                    "unknown attribute `{}` on `<{}>`.",
+                    attr.key.name, spec.name
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The value of the attribute, if it is given as a literal string.
+fn literal_attribute(attributes: &[HtmlAttribute], key: &str) -> Option<String> {
+    attributes
+        .iter()
+        .find(|attr| attr.at.is_none() && attr.key.name == key)
+        .and_then(|attr| match attr.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(ref value),
+                ..
+            }) => Some(value.value()),
+            _ => None,
+        })
+}
+
+fn validate_placement(
+    name: &str,
+    spec: &ElementSpec,
+    span: Span,
+    ancestors: &Ancestors,
+) -> ParseResult<()> {
+    if let Some(parent) = resolve_parent(spec, ancestors) {
+        return Err(Error::new(
+            span,
+            format!("`<{}>` is not allowed within `<{}>`.", name, parent.name),
+        ));
+    }
+
+    if let Some(ancestor) = ancestors.iter().find(|a| a.forbids_descendant(spec)) {
+        return Err(Error::new(
+            span,
+            format!("`<{}>` cannot be nested within `<{}>`.", name, ancestor.name),
+        ));
+    }
+    Ok(())
+}
+
+/// Finds the parent whose content model disallows the element, if any.
+fn resolve_parent(
+    spec: &ElementSpec,
+    ancestors: &Ancestors,
+) -> Option<&'static ElementSpec> {
+    for ancestor in ancestors.iter().rev() {
+        match ancestor.content {
+            Content::Transparent(extras) => {
+                if extras.contains(&spec.name) {
+                    return None;
+                }
+            }
+            content => {
+                return if content.allows(spec) {
+                    None
+                } else {
+                    Some(ancestor)
+                };
+            }
+        }
+    }
+    None
+}
+
+/// Finds the nearest parent which is not transparent along with its content
+/// model.
+fn resolve_content(ancestors: &Ancestors) -> Option<(&'static ElementSpec, Content)> {
+    ancestors
+        .iter()
+        .rev()
+        .find(|a| !matches!(a.content, Content::Transparent(_)))
+        .map(|a| (*a, a.content))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn validate(markup: &str) -> ParseResult<()> {
+        syn::parse_str::<HtmlRoot>(markup).unwrap().validate()
+    }
+
+    #[test]
+    fn should_validate_html() {
+        validate(
+            r#"
+            <div class="list">
+                <ul>
+                    <li><a href="/"><span>"Home"</span></a></li>
+                </ul>
+                <label for="name">"Name"<input name="name"></label>
+                <my-element whatever="x"><div></div></my-element>
+                <svg><path></path></svg>
+            </div>
+            "#,
+        ).unwrap();
+    }
+
+    #[test]
+    fn should_not_validate_unknown_tag() {
+        assert!(validate("<dvi></dvi>").is_err());
+    }
+
+    #[test]
+    fn should_not_validate_unknown_attribute() {
+        assert!(validate(r#"<a hreff="/"></a>"#).is_err());
+        assert!(validate(r#"<a data-hreff="/"></a>"#).is_ok());
+        assert!(validate(r#"<a onclick="go()"></a>"#).is_ok());
+        assert!(validate(r#"<a onclik="go()"></a>"#).is_err());
+    }
+
+    #[test]
+    fn should_validate_svg_elements_at_root() {
+        assert!(validate(r#"<g><path d="M0 0"></path></g>"#).is_ok());
+        assert!(validate("<path></path> <circle></circle>").is_ok());
+        assert!(validate("<div><path></path></div>").is_err());
+    }
+
+    #[test]
+    fn should_not_validate_invalid_nesting() {
+        assert!(validate("<p><div></div></p>").is_err());
+        assert!(validate("<a><span><a></a></span></a>").is_err());
+        assert!(validate("<div><li></li></div>").is_err());
+        assert!(validate(r#"<ul>"Text"</ul>"#).is_err());
+    }
+
+    #[test]
+    fn should_allow_hidden_input_within_interactive_content() {
+        assert!(validate(r#"<button><input type="hidden"></button>"#).is_ok());
+        assert!(validate(r#"<a href="/"><input type="hidden" name="id"></a>"#).is_ok());
+        assert!(validate(r#"<button><input type="text"></button>"#).is_err());
+        assert!(validate("<button><input type={kind}></button>").is_err());
+    }
+
+    #[test]
+    fn should_resolve_transparent_content() {
+        assert!(validate("<div><a><div></div></a></div>").is_ok());
+        assert!(validate("<p><a><div></div></a></p>").is_err());
+    }
+
+    #[test]
+    fn should_validate_within_control_flow() {
+        assert!(validate("<ul> for i in items { <li></li> } </ul>").is_ok());
+        assert!(validate("<p> if show { <div></div> } </p>").is_err());
+    }
+}
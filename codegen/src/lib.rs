@@ -3,17 +3,26 @@
 #![cfg_attr(feature = "cargo-clippy", warn(clippy::all))]
 //! The crate which removes most of the boilerplate from Ruukh apps.
 //!
-//! This lib defines `#[component]`, `#[derive(Lifecycle)]` and `html!` macros.
+//! This lib defines `#[component]`, `#[derive(Lifecycle)]`, `#[watch]`,
+//! `html!`, `classes!` and `styles!` macros.
 extern crate proc_macro;
 
-use crate::{component::ComponentMeta, html::HtmlRoot};
-use proc_macro2::Span;
+use crate::{
+    classes::Classes,
+    component::{ComponentArgs, ComponentItem, ComponentMeta},
+    html::HtmlRoot,
+    styles::Styles,
+    watch::{WatchArgs, Watcher},
+};
 use quote::quote;
-use syn::{parse::Error, parse_macro_input, spanned::Spanned, DeriveInput, Item};
+use syn::{parse_macro_input, DeriveInput, ImplItemMethod};
 
+mod classes;
 mod component;
 mod html;
+mod styles;
 mod suffix;
+mod watch;
 
 /// A convenient auto derive for `Lifecycle` trait. It could be simply written
 /// as `impl Lifecycle for MyComponent {}` instead, but why not save some chars.
@@ -27,10 +36,11 @@ mod suffix;
 #[proc_macro_derive(Lifecycle)]
 pub fn derive_lifecycle(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let ident = input.ident;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let expanded = quote! {
-        impl Lifecycle for #ident {}
+        impl #impl_generics Lifecycle for #ident #ty_generics #where_clause {}
     };
 
     expanded.into()
@@ -75,9 +85,96 @@ pub fn derive_lifecycle(input: proc_macro::TokenStream) -> proc_macro::TokenStre
 /// optional while passing props.
 ///
 /// 2. `#[state]` attribute: This attributes is required to define a field as a
-/// state field. If a `#[state]` or `#[state(default)]` is specified then the
-/// `Default` value of the field is used. If you want to provide a more
-/// specific value, then pass it by using `#[state(default = val)]` attribute.
+///    state field. If a `#[state]` or `#[state(default)]` is specified then the
+///    `Default` value of the field is used. If you want to provide a more
+///    specific value, then pass it by using `#[state(default = val)]` attribute.
+///    The initial state may also be created from the props of the component
+///    with `#[state(init = expr)]`, where `expr` may use the `props` reference,
+///    like `#[state(init = props.initial_value.clone())]`.
+///    With the `persist` feature of `ruukh`, a state field may be persisted
+///    across reloads with `#[state(persist = "key")]`. It is then loaded from the
+///    storage when the component is created and saved whenever it changes.
+///
+/// 3. `#[computed]` attribute: This attribute defines a field whose value is
+///    derived from the other fields, like `#[computed(deps = [items, filter])]`.
+///    Its value is computed by the `compute_<field>(&self)` method on the
+///    component, once when the component is created and then only when any of
+///    its dependencies change. Its type needs to implement `Default`. The
+///    dependencies may be props, state or computed fields declared before it.
+///
+/// The side effects of a change to a prop or a state field may be run by a
+/// method of the component marked with `#[watch(field)]`. See
+/// [watch](attr.watch.html).
+///
+/// Both `#[prop]` and `#[state]` accept a comparison option, which decides
+/// whether a field changed and the component is to be re-rendered. The
+/// fields are compared with `PartialEq` by default. Instead,
+/// `compare = "ptr"` compares the address pointed to by an `Rc`, `Box` or
+/// reference, `compare = path::to::func` uses a `fn(&T, &T) -> bool` which
+/// returns whether the values are equal and `no_compare` always treats the
+/// field as changed. For example, `#[prop(default, compare = "ptr")]`.
+///
+/// The history of the state may be recorded for undo & redo by declaring the
+/// component with `#[component(history)]`, or `#[component(history = 20)]`
+/// to keep at most 20 undo steps. A snapshot of the state is then recorded on
+/// every change through `set_state`, which is undone with `self.undo()` &
+/// redone with `self.redo()`. Multiple changes made within
+/// `self.transaction(|| ...)` are undone as a single step. The state fields
+/// are required to implement `Clone`.
+///
+/// With the `devtools` feature of `ruukh`, a component declared with
+/// `#[component(inspect)]` reports its props & state to the developer tools,
+/// which may be inspected from the browser console with `ruukh.inspect()`.
+/// Its state fields may also be edited live with
+/// `ruukh.editState("0/1", "field", "json value")`. The props are required to
+/// implement `Debug` and the state fields `Serialize` & `Deserialize`.
+/// Without the feature, `inspect` generates nothing.
+///
+/// A stateless component may also be declared as a function of its props,
+/// whose body renders the component. The parameters are prop fields, which
+/// accept the `#[prop]` attribute, and are bound as references to the props.
+/// The `Lifecycle` & `Render` traits are then implemented for it.
+/// # Example
+/// ```ignore,compile_fail
+/// #[component]
+/// fn Badge(label: String, #[prop(default)] color: Color) -> Markup<Self> {
+///     html! {
+///         <span class={color.class()}>{ label }</span>
+///     }
+/// }
+/// ```
+/// It is used in `html!` like any other component, like
+/// `<Badge label={label} />`. A function component may keep local state &
+/// run effects with the hooks in `ruukh::hooks`, like `use_state`.
+///
+/// A component may also be generic over types, which are then carried over
+/// to its generated `*Props`, `*State` & `*Events` types. The type parameters
+/// are required to be `'static`, so lifetime parameters are not allowed.
+/// # Example
+/// ```ignore,compile_fail
+/// #[component]
+/// struct List<T: Display> {
+///     items: Vec<T>,
+/// }
+/// ```
+/// It is then used in `html!` like `<List<User> items={users}></List>`.
+///
+/// You may also export the component as a Custom Element by placing
+/// `#[custom_element]` attribute below `#[component]`. Its props are then
+/// built from the attributes of the element and its events are dispatched as
+/// DOM `CustomEvent`s.
+/// # Example
+/// ```ignore,compile_fail
+/// #[component]
+/// #[custom_element]
+/// #[events(
+///     fn changed(&self, count: i32);
+/// )]
+/// struct MyCounter {
+///     #[prop(default)]
+///     step: i32,
+/// }
+/// ```
 #[proc_macro_attribute]
 #[cfg_attr(
     feature = "cargo-clippy",
@@ -87,24 +184,51 @@ pub fn component(
     metadata: proc_macro::TokenStream,
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
-    if !metadata.is_empty() {
-        return Error::new(
-            Span::call_site(),
-            "`#[component]` does not support attribute arguments.",
-        ).to_compile_error()
-        .into();
-    }
-
-    let input = parse_macro_input!(input as Item);
+    let args = parse_macro_input!(metadata as ComponentArgs);
+    let input = parse_macro_input!(input as ComponentItem);
 
     let expanded = match input {
-        Item::Struct(struct_) => ComponentMeta::parse(struct_)
-            .map(|s| s.expand())
-            .unwrap_or_else(|e| e.to_compile_error()),
-        _ => {
-            Error::new(input.span(), "Only structs are allowed to be Component").to_compile_error()
-        }
+        ComponentItem::Struct(struct_) => ComponentMeta::parse(args, struct_).map(|s| s.expand()),
+        ComponentItem::Fn(fn_) => fn_.expand(args),
     };
+    let expanded = expanded.unwrap_or_else(|e| e.to_compile_error());
+
+    expanded.into()
+}
+
+/// `#[watch(field)]` attribute to run side effects when a prop or a state
+/// field of a component changes. It is placed on a method of the component
+/// which takes references to the old & the new value, and is invoked after the
+/// change is committed to the component. A field may be watched by only a
+/// single method.
+///
+/// # Example
+/// ```ignore,compile_fail
+/// #[component]
+/// struct Search {
+///     query: String,
+///     #[state]
+///     page: u32,
+/// }
+///
+/// impl Search {
+///     #[watch(query)]
+///     fn reset_page(&self, _old: &String, _new: &String) {
+///         self.set_state(|state| state.page = 0);
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn watch(
+    metadata: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let args = parse_macro_input!(metadata as WatchArgs);
+    let input = parse_macro_input!(input as ImplItemMethod);
+
+    let expanded = Watcher::parse(args, input)
+        .map(|watcher| watcher.expand())
+        .unwrap_or_else(|e| e.to_compile_error());
 
     expanded.into()
 }
@@ -126,7 +250,8 @@ pub fn component(
 /// ```
 ///
 /// ## Self-closing tags
-/// Only html specified self-closing tags can be self-closing tags.
+/// Only html specified self-closing tags & component tags can be self-closing
+/// tags. Component tags are closed with a slash.
 ///
 /// ```ignore,compile_fail
 /// html! {
@@ -146,6 +271,7 @@ pub fn component(
 /// ```ignore,compile_fail
 /// html! {
 ///     <MyComponent></MyComponent>
+///     <MyComponent />
 /// }
 /// ```
 ///
@@ -173,8 +299,130 @@ pub fn component(
 ///     "There are "{ count }" people."
 /// }
 /// ```
+///
+/// ## Attributes
+/// Values may be expressions within braces or literals. An attribute without
+/// a value is a boolean attribute which is set, while `{class}` is a
+/// shorthand for `class={class}`. The same applies to the props of a component.
+/// ```ignore,compile_fail
+/// html! {
+///     <input class="field" name={self.name} disabled>
+///     <button {class} @click={Self::on_click}>"Submit"</button>
+/// }
+/// ```
+///
+/// ## Spreading
+/// An `AttributeMap` can be spread onto an element, while a `*Props` struct
+/// can be spread onto a component to fill in the remaining props. The
+/// explicitly given attributes or props take precedence. The spread is only
+/// borrowed & the remaining props are cloned from it, so a field of `self` may
+/// be spread as is.
+/// ```ignore,compile_fail
+/// html! {
+///     <button ..self.extra_attrs class="btn">"Close"</button>
+///     <Child ..self.child_props label="Child"></Child>
+///     <Child ..{ ChildProps!(label: "Child") } count={1}></Child>
+/// }
+/// ```
+///
+/// ## Conditionals
+/// A missing `else` renders nothing.
+/// ```ignore,compile_fail
+/// html! {
+///     if self.logged_in {
+///         <button>"Logout"</button>
+///     } else if let Some(ref name) = self.name {
+///         "Welcome back, "{ name }
+///     } else {
+///         <button>"Login"</button>
+///     }
+/// }
+/// ```
+///
+/// ## Loops
+/// A single keyed item in the body makes it a keyed list.
+/// ```ignore,compile_fail
+/// html! {
+///     <ul>
+///         for todo in self.todos.iter() {
+///             <li key={todo.id}>{ &todo.title }</li>
+///         }
+///     </ul>
+/// }
+/// ```
+///
+/// ## Matches
+/// ```ignore,compile_fail
+/// html! {
+///     match self.status {
+///         Status::Loading => "Loading...",
+///         Status::Failed(ref err) => {
+///             <div>"Failed: "{ err }</div>
+///         }
+///         Status::Done => <span>"Done"</span>
+///     }
+/// }
+/// ```
+///
+/// Braces within the expressions after `if`, `in` & `match` need to be
+/// wrapped in parentheses.
+///
+/// ## Validation
+/// The markup is validated against the HTML specification at compile time.
+/// Unknown tags, unknown attributes on a tag, invalid nesting like a `<div>`
+/// within a `<p>` & children within void elements are reported as errors.
+/// Custom elements, i.e. tags with a dash, are exempt. Attributes prefixed
+/// with `data-` & `aria-` are always allowed, as are the event handler
+/// attributes like `onclick`. SVG elements like `<path>` & `<g>` may be at the
+/// root of a markup, to be placed within an `<svg>` of another one.
+/// ```ignore,compile_fail
+/// html! {
+///     <p><div>"Not allowed."</div></p>
+/// }
+/// ```
 #[proc_macro]
 pub fn html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let parsed = parse_macro_input!(input as HtmlRoot);
+    match parsed.validate() {
+        Ok(()) => parsed.expand().into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// `classes!` macro to build a list of classes for the `class` attribute.
+/// A class followed by `=> condition` is only added if the condition holds.
+///
+/// Unlike a string, the list is diffed class by class when patched onto the
+/// DOM.
+///
+/// # Example
+/// ```ignore,compile_fail
+/// html! {
+///     <button class={classes!["btn", "btn-primary", "active" => self.active]}>
+///     </button>
+/// }
+/// ```
+#[proc_macro]
+pub fn classes(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let parsed = parse_macro_input!(input as Classes);
+    parsed.expand().into()
+}
+
+/// `styles!` macro to build a map of style properties for the `style`
+/// attribute. Custom properties may be given as strings.
+///
+/// Unlike a string, the map is diffed property by property when patched onto
+/// the DOM.
+///
+/// # Example
+/// ```ignore,compile_fail
+/// html! {
+///     <div style={styles!{ width: "100px", background-color: self.color() }}>
+///     </div>
+/// }
+/// ```
+#[proc_macro]
+pub fn styles(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let parsed = parse_macro_input!(input as Styles);
     parsed.expand().into()
 }
@@ -0,0 +1,93 @@
+//! The pseudo grammar for the styles! macro.
+//!
+//! STYLES -> STYLE , STYLES | STYLE | EPS
+//!
+//! STYLE -> PROPERTY : EXPR
+//!
+//! PROPERTY -> DASHED_IDENT | "CUSTOM_PROPERTY"
+//!
+//! DASHED_IDENT -> IDENT-DASHED_IDENT | IDENT
+//!
+//! N.B. EPS is Epsilon and IDENT & EXPR are Rust constructs.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream, Result as ParseResult},
+    punctuated::Punctuated,
+    Expr, Ident, LitStr, Token,
+};
+
+pub struct Styles {
+    pub styles: Punctuated<Style, Token![,]>,
+}
+
+impl Parse for Styles {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        Ok(Styles {
+            styles: Punctuated::parse_terminated(input)?,
+        })
+    }
+}
+
+impl Styles {
+    pub fn expand(&self) -> TokenStream {
+        let styles: Vec<_> = self.styles.iter().map(Style::expand).collect();
+        quote! {
+            {
+                let mut styles = ruukh::vdom::velement::StyleMap::new();
+                #(#styles)*
+                styles
+            }
+        }
+    }
+}
+
+pub struct Style {
+    pub property: String,
+    pub value: Expr,
+}
+
+impl Parse for Style {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        // Custom properties like `--main-color` can only be written as strings.
+        // Keywords are allowed as idents for properties like `box-sizing`.
+        let property = if input.peek(LitStr) {
+            input.parse::<LitStr>()?.value()
+        } else {
+            Punctuated::<Ident, Token![-]>::parse_separated_nonempty_with(input, Ident::parse_any)?
+                .into_iter()
+                .map(|ident| ident.to_string())
+                .collect::<Vec<_>>()
+                .join("-")
+        };
+        input.parse::<Token![:]>()?;
+        let value = input.parse()?;
+        Ok(Style { property, value })
+    }
+}
+
+impl Style {
+    fn expand(&self) -> TokenStream {
+        let property = &self.property;
+        let value = &self.value;
+        quote! {
+            styles.set(#property, #value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_styles() {
+        let parsed: Styles = syn::parse_str(
+            r#"width: "100px", background-color: self.color(), "--main-color": "red","#,
+        ).unwrap();
+        let properties: Vec<_> = parsed.styles.iter().map(|s| s.property.as_str()).collect();
+        assert_eq!(properties, vec!["width", "background-color", "--main-color"]);
+    }
+}
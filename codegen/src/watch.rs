@@ -0,0 +1,118 @@
+//! The watchers of the prop & state fields, declared on the methods of the
+//! component with `#[watch(field)]`.
+//!
+//! The watcher is invoked by the component through a hidden method named after
+//! the field. The component falls back to an empty method of a trait when the
+//! field is not watched, which the hidden method takes precedence over as an
+//! inherent method.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{Error, Parse, ParseStream, Result as ParseResult},
+    spanned::Spanned,
+    FnArg, Ident, ImplItemMethod, Type,
+};
+
+/// The field watched with `#[watch(field)]`.
+pub struct WatchArgs {
+    pub field: Ident,
+}
+
+impl Parse for WatchArgs {
+    fn parse(input: ParseStream<'_>) -> ParseResult<Self> {
+        if input.is_empty() {
+            return Err(input.error("expected the field to watch, like `#[watch(field)]`."));
+        }
+        let field = input.parse()?;
+        if !input.is_empty() {
+            return Err(input.error("only a single field may be watched by a method."));
+        }
+        Ok(WatchArgs { field })
+    }
+}
+
+/// The method which watches a field.
+pub struct Watcher {
+    field: Ident,
+    old_ty: Type,
+    new_ty: Type,
+    method: ImplItemMethod,
+}
+
+impl Watcher {
+    pub fn parse(args: WatchArgs, method: ImplItemMethod) -> ParseResult<Watcher> {
+        let (old_ty, new_ty) = {
+            let inputs: Vec<_> = method.sig.decl.inputs.iter().collect();
+            match inputs.as_slice() {
+                [FnArg::SelfRef(ref self_ref), FnArg::Captured(ref old), FnArg::Captured(ref new)]
+                    if self_ref.mutability.is_none() =>
+                {
+                    (old.ty.clone(), new.ty.clone())
+                }
+                _ => {
+                    return Err(Error::new(
+                        method.sig.decl.inputs.span(),
+                        "A watcher takes `&self`, the old & the new value, like \
+                         `fn on_change(&self, old: &T, new: &T)`.",
+                    ))
+                }
+            }
+        };
+        Ok(Watcher {
+            field: args.field,
+            old_ty,
+            new_ty,
+            method,
+        })
+    }
+
+    pub fn expand(&self) -> TokenStream {
+        let method = &self.method;
+        let vis = &method.vis;
+        let ident = &method.sig.ident;
+        let watcher = watcher_ident(&self.field);
+        let old_ty = &self.old_ty;
+        let new_ty = &self.new_ty;
+
+        quote! {
+            #method
+
+            #[doc(hidden)]
+            #vis fn #watcher(&self, __old__: #old_ty, __new__: #new_ty) {
+                self.#ident(__old__, __new__);
+            }
+        }
+    }
+}
+
+/// The hidden method through which the watcher of the `field` is invoked.
+pub fn watcher_ident(field: &Ident) -> Ident {
+    Ident::new(&format!("__watch_{}__", field), Span::call_site())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn watcher(args: &str, method: &str) -> ParseResult<Watcher> {
+        Watcher::parse(syn::parse_str(args)?, syn::parse_str(method)?)
+    }
+
+    #[test]
+    fn should_parse_a_watcher() {
+        let parsed = watcher("page", "fn on_page(&self, old: &u32, new: &u32) {}").unwrap();
+        assert_eq!(parsed.field, "page");
+        let expanded = parsed.expand().to_string();
+        assert!(expanded.contains("fn __watch_page__"));
+    }
+
+    #[test]
+    fn should_not_parse_invalid_watchers() {
+        assert!(syn::parse_str::<WatchArgs>("").is_err());
+        assert!(syn::parse_str::<WatchArgs>("page, query").is_err());
+        assert!(watcher("page", "fn on_page(&self, new: &u32) {}").is_err());
+        assert!(watcher("page", "fn on_page(&mut self, old: &u32, new: &u32) {}").is_err());
+        assert!(watcher("page", "fn on_page(old: &u32, new: &u32, x: u32) {}").is_err());
+    }
+}
@@ -1,6 +1,6 @@
 #![feature(proc_macro_hygiene, decl_macro)]
 
-use ruukh::prelude::*;
+use ruukh::{prelude::*, vdom::velement::StyleMap};
 use wasm_bindgen::prelude::*;
 use web_sys::Event;
 
@@ -15,12 +15,8 @@ impl Render for MainApp {
     fn render(&self) -> Markup<Self> {
         html! {
             <div style={self.style()}>{ if self.toggle { "On" } else { "Off" } }</div>
-            <Button 
-                style={r#"
-                    background: gray; 
-                    color: white;
-                    padding: 4px;
-                "#} 
+            <Button
+                style="background: gray; color: white; padding: 4px;"
                 @click={Self::toggle}
             ></Button>
         }
@@ -28,11 +24,11 @@ impl Render for MainApp {
 }
 
 impl MainApp {
-    fn style(&self) -> &'static str {
-        if self.toggle {
-            "width: 100px; height: 100px; background-color: red;"
-        } else {
-            "width: 100px; height: 100px; background-color: yellow;"
+    fn style(&self) -> StyleMap {
+        styles! {
+            width: "100px",
+            height: "100px",
+            background-color: if self.toggle { "red" } else { "yellow" },
         }
     }
 
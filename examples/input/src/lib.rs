@@ -14,17 +14,11 @@ struct MainApp {
 impl Render for MainApp {
     fn render(&self) -> Markup<Self> {
         html! {
-            Name: <input @input={Self::on_input}/>
-            {
-                if !self.input.is_empty() {
-                    html! {
-                        <div>
-                            "Your name is "{ &self.input }"."
-                        </div>
-                    }
-                } else {
-                    html!()
-                }
+            "Name: "<input @input={Self::on_input}/>
+            if !self.input.is_empty() {
+                <div>
+                    "Your name is "{ &self.input }"."
+                </div>
             }
         }
     }
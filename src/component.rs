@@ -60,7 +60,11 @@
 //! Note: Docs on component macros are located
 //! [here](../../ruukh_codegen/index.html).
 
-use crate::{Markup, MessageSender, Shared};
+#[cfg(feature = "devtools")]
+use crate::devtools::Inspection;
+use crate::{
+    error::CaughtError, history::History, suspense::Loading, Markup, MessageSender, Shared,
+};
 
 /// Trait to define a component. You do not need to implement this trait. Auto
 /// implement this trait by using `#[component]` on a component struct (which
@@ -113,10 +117,21 @@ pub trait Component: 'static {
     /// to it. The props are used as is whereas the events passed are converted
     /// to `Self::Events` types from the `Self::Events::Other` type.
     ///
-    /// It also creates a `Default::default()` state along with wiring up
-    /// change notifying mechanism into `status`.
+    /// It also takes the state created by `initial_state` along with wiring
+    /// up change notifying mechanism from `status`.
     fn init(props: Self::Props, events: Self::Events, status: Status<Self::State>) -> Self;
 
+    /// Creates the initial state of the component from the props passed to
+    /// it. The state is `Default::default()` unless overridden.
+    ///
+    /// ## Internals
+    ///
+    /// It is overridden when any state field is given an `#[state(init =
+    /// expr)]` attribute, where `expr` may use the `props` passed.
+    fn initial_state(_props: &Self::Props) -> Self::State {
+        Self::State::default()
+    }
+
     /// Updates the component with newer props & events and returns older props
     /// (if changed).
     ///
@@ -133,6 +148,29 @@ pub trait Component: 'static {
 
     /// Get the status of the component.
     fn status(&self) -> Option<&Shared<Status<Self::State>>>;
+
+    /// Reports the props & state of the component to the developer tools.
+    /// Returns `None` unless the component is declared with
+    /// `#[component(inspect)]`.
+    #[cfg(feature = "devtools")]
+    fn inspect(&self) -> Option<Inspection> {
+        None
+    }
+
+    /// Sets the state `field` to the `value` given as JSON, from the developer
+    /// tools.
+    ///
+    /// ## Internals
+    ///
+    /// The value is set on the state of the status, as done by `set_state`,
+    /// so that the component refreshes its state on the next render.
+    #[cfg(feature = "devtools")]
+    fn edit_state(&self, field: &str, _value: &str) -> Result<(), String> {
+        Err(format!(
+            "Cannot edit `{}` of a component which is not inspectable.",
+            field
+        ))
+    }
 }
 
 /// Trait to allow mutatation of a component state.
@@ -196,6 +234,52 @@ pub trait StateSetter {
     fn state_setter(&self) -> Self::Setter;
 }
 
+/// Trait to act upon the updates of a component, once they are applied to the
+/// DOM. It is implemented for every component.
+///
+/// # Example
+/// ```ignore
+/// self.set_state_then(
+///     |state| state.editing = true,
+///     move || input.focus().unwrap(),
+/// );
+/// ```
+pub trait Update: Component {
+    /// Invokes the callback after the next render of the app, once the
+    /// patches are applied to the DOM.
+    ///
+    /// A component without any props or state goes through the App it is
+    /// rendered or handles an event in.
+    fn next_tick(&self, callback: impl FnOnce() + 'static) {
+        match self.status() {
+            Some(status) => status.borrow().next_tick(callback),
+            None => crate::next_tick(callback),
+        }
+    }
+
+    /// Mutates the state like `set_state` and invokes the callback once the
+    /// change is applied to the DOM.
+    fn set_state_then(
+        &self,
+        mutator: impl FnMut(&mut <Self as SetState>::State),
+        callback: impl FnOnce() + 'static,
+    ) where
+        Self: SetState,
+    {
+        self.set_state(mutator);
+        self.next_tick(callback);
+    }
+
+    /// Re-renders the component, even if its props & state are unchanged.
+    fn force_update(&self) {
+        if let Some(status) = self.status() {
+            status.borrow_mut().force_update();
+        }
+    }
+}
+
+impl<COMP: Component> Update for COMP {}
+
 /// Stores the state of the component along with the flags to identify whether
 /// the props and state are dirty. Also provides a mechanism to notify the app
 /// of state changes.
@@ -203,7 +287,9 @@ pub struct Status<T> {
     state: T,
     state_dirty: bool,
     props_dirty: bool,
+    forced: bool,
     rx_sender: MessageSender,
+    history: Option<History<T>>,
 }
 
 impl<T> Status<T> {
@@ -213,7 +299,9 @@ impl<T> Status<T> {
             state,
             state_dirty: false,
             props_dirty: false,
+            forced: false,
             rx_sender,
+            history: None,
         }
     }
 
@@ -237,6 +325,16 @@ impl<T> Status<T> {
         self.props_dirty
     }
 
+    /// Set whether the component is forced to re-render.
+    pub fn set_forced(&mut self, value: bool) {
+        self.forced = value;
+    }
+
+    /// Is the component forced to re-render.
+    pub fn is_forced(&self) -> bool {
+        self.forced
+    }
+
     /// Gets the state immutably.
     pub fn state_as_ref(&self) -> &T {
         &self.state
@@ -251,6 +349,70 @@ impl<T> Status<T> {
     pub fn do_react(&self) {
         self.rx_sender.do_react();
     }
+
+    /// Forces the component to re-render, even if its state is unchanged.
+    pub fn force_update(&mut self) {
+        self.forced = true;
+        self.do_react();
+    }
+
+    /// Invokes the callback after the App is rendered next, once the patches
+    /// are applied to the DOM.
+    pub fn next_tick(&self, callback: impl FnOnce() + 'static) {
+        self.rx_sender.next_tick(callback);
+    }
+
+    /// Starts recording the history of the state, keeping at most `limit`
+    /// undo steps.
+    pub fn enable_history(&mut self, limit: usize) {
+        self.history = Some(History::new(limit));
+    }
+
+    /// Gets the history of the state, if recorded.
+    pub fn history(&self) -> Option<&History<T>> {
+        self.history.as_ref()
+    }
+
+    /// Gets the history of the state mutably, if recorded.
+    pub fn history_mut(&mut self) -> Option<&mut History<T>> {
+        self.history.as_mut()
+    }
+
+    /// Records the snapshot of the state `before` a change, if the history is
+    /// recorded.
+    pub fn record_history(&mut self, before: T) {
+        if let Some(ref mut history) = self.history {
+            history.record(before);
+        }
+    }
+
+    /// Restores the state to the last recorded snapshot and requests the App
+    /// to react to it. Returns whether there was anything to undo.
+    pub fn undo(&mut self) -> bool {
+        let undone = match self.history {
+            Some(ref mut history) => history.undo(&mut self.state),
+            None => false,
+        };
+        if undone {
+            self.set_state_dirty(true);
+            self.do_react();
+        }
+        undone
+    }
+
+    /// Restores the state to the last undone snapshot and requests the App to
+    /// react to it. Returns whether there was anything to redo.
+    pub fn redo(&mut self) -> bool {
+        let redone = match self.history {
+            Some(ref mut history) => history.redo(&mut self.state),
+            None => false,
+        };
+        if redone {
+            self.set_state_dirty(true);
+            self.do_react();
+        }
+        redone
+    }
 }
 
 /// The lifecycle of a stateful component.
@@ -261,21 +423,77 @@ pub trait Lifecycle: Component {
     /// Invoked when the component is first created.
     fn created(&self) {}
 
+    /// Invoked after the component is created, to load the data it renders.
+    ///
+    /// Return a future to suspend the component till it resolves. It is
+    /// rendered & mounted only after that, while the nearest suspense boundary
+    /// above it shows its fallback. See the `suspense` module.
+    ///
+    /// It is invoked only once, so a change in the props does not load the
+    /// data again. Load it from `updated` instead, when the data depends on
+    /// the props.
+    fn load(&self) -> Option<Loading> {
+        None
+    }
+
     /// Invoked when the component props are updated.
     #[allow(unused_variables)]
     fn updated(&self, old_props: Self::Props) {}
 
+    /// Invoked before the component is re-rendered due to a change in its
+    /// props or state. The older props are passed if the props changed.
+    ///
+    /// Return `false` to skip the re-render, when the change does not affect
+    /// the view. The previous render is then reused, though the components
+    /// within it are still updated.
+    #[allow(unused_variables)]
+    fn should_render(&self, old_props: Option<&Self::Props>) -> bool {
+        true
+    }
+
+    /// Invoked right before the component is re-rendered due to a change in
+    /// its props or state.
+    fn before_update(&self) {}
+
     /// Invoked when the component is mounted onto the DOM tree.
     fn mounted(&self) {}
 
+    /// Invoked after each successful patch of the component onto the DOM tree,
+    /// along with the components within it. `first_render` is true only for
+    /// the patch which mounted it.
+    #[allow(unused_variables)]
+    fn rendered(&self, first_render: bool) {}
+
+    /// Invoked before the component is removed from the DOM tree, while its
+    /// nodes are still attached.
+    fn before_destroy(&self) {}
+
     /// Invoked when the component is removed from the DOM tree.
     fn destroyed(&self) {}
+
+    /// Invoked when a component within it failed, after its error fallback is
+    /// shown. See the `error` module.
+    #[allow(unused_variables)]
+    fn caught_error(&self, error: CaughtError) {}
 }
 
 /// Trait to render a view for the component.
 pub trait Render: Lifecycle + Sized {
     /// Render a markup for the component by using the html! macro.
     fn render(&self) -> Markup<Self>;
+
+    /// The markup shown in place of the render while any of the components
+    /// within it are loading, which makes the component a suspense boundary.
+    fn fallback(&self) -> Option<Markup<Self>> {
+        None
+    }
+
+    /// The markup shown in place of the render when any of the components
+    /// within it fail, which makes the component an error boundary.
+    #[allow(unused_variables)]
+    fn error_fallback(&self, error: &CaughtError) -> Option<Markup<Self>> {
+        None
+    }
 }
 
 /// Trait to convert from a event props to a events type.
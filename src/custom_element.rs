@@ -0,0 +1,471 @@
+//! Export Ruukh components as standard
+//! [Custom Elements](https://developer.mozilla.org/en-US/docs/Web/Web_Components/Using_custom_elements),
+//! so that they can be embedded in pages built with any other framework.
+//!
+//! A component opts in by placing `#[custom_element]` below `#[component]`.
+//! Then its observed attributes are mapped onto its prop fields (a field
+//! `step_size` is observed as `step-size` attribute) and its events are
+//! re-emitted as DOM `CustomEvent`s on the host element (an event
+//! `value_changed` is dispatched as `value-changed` with the arguments as its
+//! `detail`).
+//!
+//! # Example
+//! ```
+//! # #![feature(proc_macro_hygiene, decl_macro)]
+//! # use ruukh::prelude::*;
+//! # use wasm_bindgen::prelude::*;
+//! #
+//! #[component]
+//! #[custom_element]
+//! #[derive(Lifecycle)]
+//! #[events(
+//!     fn changed(&self, count: i32);
+//! )]
+//! struct MyCounter {
+//!     #[prop(default)]
+//!     step: i32,
+//!     #[state]
+//!     count: i32,
+//! }
+//!
+//! impl Render for MyCounter {
+//!     fn render(&self) -> Markup<Self> {
+//!         html! {
+//!             "The count is: "{ self.count }
+//!         }
+//!     }
+//! }
+//!
+//! #[wasm_bindgen]
+//! pub fn run() {
+//!     CustomElement::<MyCounter>::new().define("my-counter");
+//! }
+//! ```
+//!
+//! The prop types need to implement [FromAttribute](trait.FromAttribute.html)
+//! and the event arguments need to be convertible into a `JsValue`. An
+//! attribute value which cannot be converted is reported as an error, instead
+//! of the default of the prop being used.
+
+use crate::{
+    component::{FromEventProps, Render, RootParent},
+    error,
+    vdom::vcomponent::{ComponentManager, ComponentWrapper},
+    MessageListener, MessageSender, Shared,
+};
+use js_sys::{Array, Function, Object, Reflect};
+use std::{
+    cell::{Cell, RefCell},
+    fmt::{self, Display, Formatter},
+    marker::PhantomData,
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{
+    console, CustomEvent, CustomEventInit, EventTarget, HtmlElement, Node, ShadowRootInit,
+    ShadowRootMode,
+};
+
+#[wasm_bindgen(inline_js = r#"
+export function define_custom_element(tag, observed, create) {
+    customElements.define(tag, class extends HTMLElement {
+        static get observedAttributes() {
+            return observed;
+        }
+
+        constructor() {
+            super();
+            this.__ruukh__ = create(this);
+        }
+
+        connectedCallback() {
+            this.__ruukh__.connected();
+        }
+
+        disconnectedCallback() {
+            this.__ruukh__.disconnected();
+        }
+
+        attributeChangedCallback() {
+            this.__ruukh__.attributeChanged();
+        }
+    });
+}
+"#)]
+extern "C" {
+    fn define_custom_element(tag: &str, observed: &Array, create: &Function);
+}
+
+/// Registers a component as a Custom Element.
+pub struct CustomElement<COMP>
+where
+    COMP: Render,
+    COMP::Props: FromAttributes,
+    COMP::Events: DispatchEvents,
+{
+    shadow_root: bool,
+    _phantom: PhantomData<COMP>,
+}
+
+impl<COMP> CustomElement<COMP>
+where
+    COMP: Render,
+    COMP::Props: FromAttributes,
+    COMP::Events: DispatchEvents,
+{
+    /// Create a new Custom Element definition with a `Component` struct
+    /// passed as its type parameter. By default, the component is rendered in
+    /// the light DOM of the host element.
+    pub fn new() -> CustomElement<COMP> {
+        Default::default()
+    }
+
+    /// Render the component in an open shadow root attached to the host
+    /// element instead.
+    pub fn with_shadow_root(mut self) -> CustomElement<COMP> {
+        self.shadow_root = true;
+        self
+    }
+
+    /// Defines the Custom Element with the given tag name. The tag name must
+    /// contain a dash as required by the spec.
+    pub fn define(self, tag: &str) {
+        let shadow_root = self.shadow_root;
+        let observed: Array = COMP::Props::OBSERVED_ATTRIBUTES
+            .iter()
+            .map(|name| JsValue::from_str(name))
+            .collect();
+        let create: Closure<dyn Fn(HtmlElement) -> JsValue> =
+            Closure::wrap(Box::new(move |element| {
+                Host::<COMP>::new(element, shadow_root).into_callbacks()
+            }));
+        define_custom_element(tag, &observed, create.as_ref().unchecked_ref());
+
+        // Leak the closure as a Custom Element cannot be undefined.
+        create.forget();
+    }
+}
+
+impl<COMP> Default for CustomElement<COMP>
+where
+    COMP: Render,
+    COMP::Props: FromAttributes,
+    COMP::Events: DispatchEvents,
+{
+    fn default() -> Self {
+        CustomElement {
+            shadow_root: false,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// A single instance of a Custom Element which manages the component rendered
+/// within it.
+struct Host<COMP: Render>
+where
+    COMP::Events: FromEventProps<RootParent>,
+{
+    element: HtmlElement,
+    /// The host element or its shadow root.
+    root: Node,
+    is_connected: Cell<bool>,
+    /// Whether the attributes changed after the last render.
+    is_props_dirty: Cell<bool>,
+    manager: RefCell<Option<ComponentWrapper<COMP, RootParent>>>,
+    root_parent: Shared<RootParent>,
+    rx_sender: MessageSender,
+    listener: RefCell<Option<MessageListener>>,
+}
+
+impl<COMP> Host<COMP>
+where
+    COMP: Render,
+    COMP::Props: FromAttributes,
+    COMP::Events: DispatchEvents,
+{
+    fn new(element: HtmlElement, shadow_root: bool) -> Rc<Host<COMP>> {
+        let root: Node = if shadow_root {
+            element
+                .attach_shadow(&ShadowRootInit::new(ShadowRootMode::Open))
+                .unwrap()
+                .into()
+        } else {
+            element.clone().into()
+        };
+        let (receiver, rx_sender) = crate::app_message_channel();
+
+        let host = Rc::new(Host {
+            element,
+            root,
+            is_connected: Cell::new(false),
+            is_props_dirty: Cell::new(true),
+            manager: RefCell::new(None),
+            root_parent: Rc::new(RefCell::new(())),
+            rx_sender,
+            listener: RefCell::new(None),
+        });
+
+        // Rerender when it receives update messages. The listener is owned by
+        // the host, so it only keeps a weak reference back. There is no one to
+        // pass the error on to, so it is logged instead.
+        let weak_host: Weak<Host<COMP>> = Rc::downgrade(&host);
+        let listener = receiver.listen(move || {
+            if let Some(host) = weak_host.upgrade() {
+                if host.is_connected.get() {
+                    if let Err(error) = host.render() {
+                        console::error_1(&error);
+                    }
+                }
+            }
+        });
+        *host.listener.borrow_mut() = Some(listener);
+        host
+    }
+
+    /// Creates the callbacks invoked by the Custom Element class. An error is
+    /// thrown from the callback, for the page to see.
+    fn into_callbacks(self: Rc<Self>) -> JsValue {
+        let connected = {
+            let host = self.clone();
+            Closure::wrap(Box::new(move || {
+                host.is_connected.set(true);
+                host.render()
+            }) as Box<dyn Fn() -> Result<(), JsValue>>)
+        };
+        let disconnected = {
+            let host = self.clone();
+            Closure::wrap(Box::new(move || {
+                host.is_connected.set(false);
+                host.unmount()
+            }) as Box<dyn Fn() -> Result<(), JsValue>>)
+        };
+        let attribute_changed = {
+            let host = self;
+            Closure::wrap(Box::new(move || {
+                // The attributes may be changed by an event listener while the
+                // component is still rendering, so only request a rerender.
+                host.is_props_dirty.set(true);
+                if host.is_connected.get() {
+                    host.rx_sender.do_react();
+                }
+            }) as Box<dyn Fn()>)
+        };
+
+        let callbacks = Object::new();
+        for (name, callback) in [
+            ("connected", connected.into_js_value()),
+            ("disconnected", disconnected.into_js_value()),
+            ("attributeChanged", attribute_changed.into_js_value()),
+        ]
+            .iter()
+        {
+            Reflect::set(&callbacks, &JsValue::from_str(name), callback).unwrap();
+        }
+        callbacks.into()
+    }
+
+    /// Mounts the component or updates it with the changed attributes and
+    /// renders it.
+    fn render(&self) -> Result<(), JsValue> {
+        let mut manager = self.manager.borrow_mut();
+        if self.is_props_dirty.replace(false) {
+            let element = &self.element;
+            // Wait for the required attributes, if not available yet.
+            let props = COMP::Props::from_attributes(&|name| element.get_attribute(name))
+                .map_err(|invalid| JsValue::from_str(&invalid.to_string()))?;
+            if let Some(props) = props {
+                let events = COMP::Events::dispatch_to(element.as_ref());
+                let mut updated = ComponentWrapper::new(props, events);
+                if let Some(ref mut old) = *manager {
+                    updated.patch(Some(old), &self.root, None, self.root_parent.clone())?;
+                }
+                *manager = Some(updated);
+            }
+        }
+        if let Some(ref mut manager) = *manager {
+            self.rx_sender.enter(|| {
+                error::from_root(|| {
+                    manager.render_walk(
+                        &self.root,
+                        None,
+                        self.root_parent.clone(),
+                        self.rx_sender.clone(),
+                    )
+                })
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Removes the component from the host. It is mounted afresh if the host is
+    /// connected again.
+    fn unmount(&self) -> Result<(), JsValue> {
+        if let Some(manager) = self.manager.borrow_mut().take() {
+            manager.remove(&self.root)?;
+        }
+        self.is_props_dirty.set(true);
+        Ok(())
+    }
+}
+
+/// Trait to build the props of a Custom Element from its attributes.
+///
+/// It is automatically implemented on the props of a `#[custom_element]`.
+pub trait FromAttributes: Sized {
+    /// The names of the attributes which are mapped onto the props.
+    const OBSERVED_ATTRIBUTES: &'static [&'static str];
+
+    /// Builds the props by getting the value of each attribute. Returns `None`
+    /// if a required attribute is missing, or an error if any attribute is
+    /// invalid.
+    fn from_attributes(
+        attribute: &dyn Fn(&str) -> Option<String>,
+    ) -> Result<Option<Self>, InvalidAttribute>;
+}
+
+impl FromAttributes for () {
+    const OBSERVED_ATTRIBUTES: &'static [&'static str] = &[];
+
+    fn from_attributes(
+        _: &dyn Fn(&str) -> Option<String>,
+    ) -> Result<Option<Self>, InvalidAttribute> {
+        Ok(Some(()))
+    }
+}
+
+/// An attribute whose value cannot be converted to its prop.
+#[derive(Debug, PartialEq)]
+pub struct InvalidAttribute {
+    /// The name of the attribute.
+    pub name: &'static str,
+    /// The value of the attribute.
+    pub value: String,
+}
+
+impl Display for InvalidAttribute {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The value `{}` of the attribute `{}` is invalid.",
+            self.value, self.name
+        )
+    }
+}
+
+/// Converts the `value` of the attribute `name` to a prop value.
+pub fn convert_attribute<T: FromAttribute>(
+    name: &'static str,
+    value: String,
+) -> Result<T, InvalidAttribute> {
+    T::from_attribute(Some(value.clone())).ok_or(InvalidAttribute { name, value })
+}
+
+/// Trait to convert the value of an attribute to a prop value.
+pub trait FromAttribute: Sized {
+    /// Converts the value of an attribute, which is `None` when the attribute
+    /// is absent. Returns `None` if it cannot be converted.
+    fn from_attribute(value: Option<String>) -> Option<Self>;
+}
+
+impl FromAttribute for String {
+    fn from_attribute(value: Option<String>) -> Option<Self> {
+        value
+    }
+}
+
+impl FromAttribute for bool {
+    /// A boolean attribute is true when present, regardless of its value.
+    fn from_attribute(value: Option<String>) -> Option<Self> {
+        Some(value.is_some())
+    }
+}
+
+impl<T: FromAttribute> FromAttribute for Option<T> {
+    fn from_attribute(value: Option<String>) -> Option<Self> {
+        match value {
+            Some(value) => T::from_attribute(Some(value)).map(Some),
+            None => Some(None),
+        }
+    }
+}
+
+macro_rules! impl_from_attribute_with_parse {
+    ($($t:ty),*) => {
+        $(
+            impl FromAttribute for $t {
+                fn from_attribute(value: Option<String>) -> Option<Self> {
+                    value.and_then(|value| value.trim().parse().ok())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_attribute_with_parse!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, char
+);
+
+/// Trait to build the events of a Custom Element which dispatch DOM
+/// `CustomEvent`s on the host element.
+///
+/// It is automatically implemented on the events of a `#[custom_element]`.
+pub trait DispatchEvents: FromEventProps<RootParent> {
+    /// Builds the event props which dispatch the events on the `target`.
+    fn dispatch_to(target: &EventTarget) -> Self::From;
+}
+
+impl DispatchEvents for () {
+    fn dispatch_to(_: &EventTarget) -> Self::From {}
+}
+
+/// Dispatches a bubbling & composed `CustomEvent` on the target. The `detail`
+/// of the event is `null` if there are no arguments, the argument itself if
+/// there is a single one, otherwise an array of the arguments.
+pub fn dispatch_event(target: &EventTarget, type_: &str, mut args: Vec<JsValue>) {
+    let detail = match args.len() {
+        0 => JsValue::null(),
+        1 => args.remove(0),
+        _ => args.into_iter().collect::<Array>().into(),
+    };
+    let init = CustomEventInit::new();
+    init.set_bubbles(true);
+    init.set_composed(true);
+    init.set_detail(&detail);
+    let event = CustomEvent::new_with_event_init_dict(type_, &init).unwrap();
+    target.dispatch_event(&event).unwrap();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_convert_attribute_to_prop_values() {
+        assert_eq!(String::from_attribute(Some("text".into())), Some("text".into()));
+        assert_eq!(String::from_attribute(None), None);
+        assert_eq!(i32::from_attribute(Some(" 42 ".into())), Some(42));
+        assert_eq!(i32::from_attribute(Some("forty-two".into())), None);
+        assert_eq!(bool::from_attribute(Some("".into())), Some(true));
+        assert_eq!(bool::from_attribute(None), Some(false));
+        assert_eq!(Option::<u8>::from_attribute(Some("7".into())), Some(Some(7)));
+        assert_eq!(Option::<u8>::from_attribute(None), Some(None));
+    }
+
+    #[test]
+    fn should_report_invalid_attribute_values() {
+        assert_eq!(convert_attribute::<i32>("step", "2".into()), Ok(2));
+        let invalid = convert_attribute::<i32>("step", "two".into()).unwrap_err();
+        assert_eq!(
+            invalid,
+            InvalidAttribute {
+                name: "step",
+                value: "two".into(),
+            }
+        );
+        assert_eq!(
+            invalid.to_string(),
+            "The value `two` of the attribute `step` is invalid."
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! Developer tools to inspect the component tree & the state at runtime.
+//!
+//! A component declared with `#[component(inspect)]` reports its props,
+//! formatted with `Debug`, and its state, serialized as JSON, so their types
+//! are required to implement `Debug` & `Serialize` respectively. The state
+//! fields may also be edited live, for which they are required to implement
+//! `Deserialize`. Any other component is reported by its type name alone.
+//!
+//! Once an app is mounted, the helpers are available in the browser console
+//! on `window.ruukh`:
+//!
+//! ```js
+//! // The tree of all the mounted apps.
+//! ruukh.inspect();
+//! // Sets the `count` state of the second child component of the first app.
+//! ruukh.editState("0/1", "count", "5");
+//! ```
+//!
+//! A component is located by a path of indexes, starting with the index of
+//! the app followed by its index among the child components at each level.
+use crate::{
+    component::{Render, RootParent},
+    vdom::{vcomponent::ComponentManager, Key, VNode},
+    Shared,
+};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::{
+    cell::RefCell,
+    fmt::Debug,
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::{prelude::*, JsCast};
+
+/// The props & state of an inspectable component.
+#[derive(Debug, Default)]
+pub struct Inspection {
+    /// The props formatted with `Debug`.
+    pub props: Map<String, Value>,
+    /// The state serialized as JSON.
+    pub state: Map<String, Value>,
+}
+
+/// A component in the component tree.
+#[derive(Debug)]
+pub struct Node {
+    /// The type name of the component.
+    pub name: &'static str,
+    /// The key of the component, if it is within a list.
+    pub key: Option<Key>,
+    /// The props & state, if the component is inspectable.
+    pub inspection: Option<Inspection>,
+    /// The child components.
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    /// Converts the component tree to JSON.
+    pub fn to_json(&self) -> Value {
+        let (props, state) = match self.inspection {
+            Some(ref inspection) => (
+                Value::Object(inspection.props.clone()),
+                Value::Object(inspection.state.clone()),
+            ),
+            None => (Value::Null, Value::Null),
+        };
+        json!({
+            "name": self.name,
+            "key": self.key.as_ref().map(key_value),
+            "props": props,
+            "state": state,
+            "children": self.children.iter().map(Node::to_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+fn key_value(key: &Key) -> Value {
+    match key {
+        Key::I32(key) => json!(key),
+        Key::I64(key) => json!(key),
+        Key::U32(key) => json!(key),
+        Key::U64(key) => json!(key),
+        Key::String(key) => json!(key),
+    }
+}
+
+/// Formats a prop with `Debug`.
+pub fn debug_value(value: &impl Debug) -> Value {
+    Value::String(format!("{:?}", value))
+}
+
+/// Serializes a state field as JSON. It is `null` if it could not be
+/// serialized.
+pub fn json_value(value: &impl Serialize) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+/// Deserializes the value of a state field from JSON.
+pub fn from_json<T: serde::de::DeserializeOwned>(value: &str) -> Result<T, String> {
+    serde_json::from_str(value).map_err(|e| format!("Invalid value: {}", e))
+}
+
+/// The components found in the `vnode`, along with their keys, in the order of
+/// their appearance.
+pub(crate) fn child_components<RCTX: Render>(
+    vnode: &VNode<RCTX>,
+) -> Vec<(Option<&Key>, &dyn ComponentManager<RenderContext = RCTX>)> {
+    let mut components = vec![];
+    collect_components(vnode, None, &mut components);
+    components
+}
+
+fn collect_components<'a, RCTX: Render>(
+    vnode: &'a VNode<RCTX>,
+    key: Option<&'a Key>,
+    components: &mut Vec<(Option<&'a Key>, &'a dyn ComponentManager<RenderContext = RCTX>)>,
+) {
+    match vnode {
+        VNode::Element(element) => collect_components(element.child(), None, components),
+        VNode::List(list) => {
+            for (key, vnode) in list.iter() {
+                collect_components(vnode, Some(key), components);
+            }
+        }
+        VNode::Component(component) => components.push((key, component.manager())),
+        VNode::Text(_) | VNode::Raw(_) | VNode::None => {}
+    }
+}
+
+type Root = RefCell<dyn ComponentManager<RenderContext = RootParent>>;
+
+thread_local! {
+    static ROOTS: RefCell<Vec<Weak<Root>>> = RefCell::new(vec![]);
+}
+
+/// Registers a mounted app to be inspected. The console helpers are installed
+/// along with the first app.
+pub(crate) fn register(root: &Shared<impl ComponentManager<RenderContext = RootParent>>) {
+    let root: Rc<Root> = root.clone();
+    let is_first = ROOTS.with(|roots| {
+        let mut roots = roots.borrow_mut();
+        roots.push(Rc::downgrade(&root));
+        roots.len() == 1
+    });
+    if is_first {
+        install_console_helpers();
+    }
+}
+
+/// Inspects the component trees of all the mounted apps.
+pub fn inspect() -> Vec<Node> {
+    ROOTS.with(|roots| {
+        roots
+            .borrow()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter_map(|root| root.try_borrow().ok().map(|root| root.inspect(None)))
+            .collect()
+    })
+}
+
+/// Edits the state `field` of the component at the `path`, like `"0/1"`, to
+/// the `value` given as JSON.
+pub fn edit_state(path: &str, field: &str, value: &str) -> Result<(), String> {
+    let path = parse_path(path)?;
+    let (&index, rest) = path
+        .split_first()
+        .ok_or_else(|| "The path is empty.".to_string())?;
+    let root = ROOTS
+        .with(|roots| roots.borrow().get(index).and_then(Weak::upgrade))
+        .ok_or_else(|| format!("There is no app at {}.", index))?;
+    let root = root
+        .try_borrow()
+        .map_err(|_| "The app is being rendered.".to_string())?;
+    root.edit_state(rest, field, value)
+}
+
+fn parse_path(path: &str) -> Result<Vec<usize>, String> {
+    path.split('/')
+        .filter(|index| !index.is_empty())
+        .map(|index| {
+            index
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid index `{}` in the path.", index))
+        }).collect()
+}
+
+/// Installs the `inspect` & `editState` helpers on `window.ruukh`.
+fn install_console_helpers() {
+    let window = match web_sys::window() {
+        Some(window) => window,
+        None => return,
+    };
+
+    let inspect = Closure::wrap(Box::new(|| {
+        let tree: Vec<_> = inspect().iter().map(Node::to_json).collect();
+        js_sys::JSON::parse(&Value::Array(tree).to_string()).unwrap_or(JsValue::NULL)
+    }) as Box<dyn Fn() -> JsValue>);
+    let edit_state = Closure::wrap(Box::new(|path: String, field: String, value: String| {
+        if let Err(e) = edit_state(&path, &field, &value) {
+            wasm_bindgen::throw_str(&e);
+        }
+    }) as Box<dyn Fn(String, String, String)>);
+
+    let helpers = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&helpers, &"inspect".into(), inspect.as_ref().unchecked_ref());
+    let _ = js_sys::Reflect::set(
+        &helpers,
+        &"editState".into(),
+        edit_state.as_ref().unchecked_ref(),
+    );
+    let _ = js_sys::Reflect::set(&window, &"ruukh".into(), &helpers);
+
+    // The helpers live as long as the page.
+    inspect.forget();
+    edit_state.forget();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_convert_tree_to_json() {
+        let mut inspection = Inspection::default();
+        inspection.props.insert("name".into(), debug_value(&"Ruukh"));
+        inspection.state.insert("count".into(), json_value(&vec![1, 2]));
+        let tree = Node {
+            name: "app::List",
+            key: None,
+            inspection: None,
+            children: vec![Node {
+                name: "app::Item",
+                key: Some(Key::new("first")),
+                inspection: Some(inspection),
+                children: vec![],
+            }],
+        };
+
+        assert_eq!(
+            tree.to_json(),
+            json!({
+                "name": "app::List",
+                "key": null,
+                "props": null,
+                "state": null,
+                "children": [{
+                    "name": "app::Item",
+                    "key": "first",
+                    "props": { "name": "\"Ruukh\"" },
+                    "state": { "count": [1, 2] },
+                    "children": [],
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn should_parse_path() {
+        assert_eq!(parse_path("0/1/2"), Ok(vec![0, 1, 2]));
+        assert_eq!(parse_path("0"), Ok(vec![0]));
+        assert!(parse_path("0/first").is_err());
+    }
+
+    #[test]
+    fn should_deserialize_state_value() {
+        assert_eq!(from_json::<i32>("5"), Ok(5));
+        assert!(from_json::<i32>("\"5\"").is_err());
+    }
+}
@@ -37,9 +37,24 @@ where
     /// current VDOM.
     fn reorder(&self, parent: &Self::Node, next: Option<&Self::Node>) -> Result<(), JsValue>;
 
-    /// Removes the VDOM from the actual DOM.
+    /// Removes the VDOM from the actual DOM. The nodes not within the `parent`,
+    /// as may be left by a failed patch, are skipped.
     fn remove(&self, parent: &Self::Node) -> Result<(), JsValue>;
 
     /// Gets the node value of the DOM attached VDOM.
     fn node(&self) -> Option<&Node>;
 }
+
+/// Counts a DOM operation issued by a patch, for the profiler. It is a no-op
+/// unless the `profiler` feature is enabled.
+#[inline]
+pub(crate) fn count_op() {
+    #[cfg(feature = "profiler")]
+    crate::profiler::count_dom_op();
+}
+
+/// Whether the `node` is a child of the `parent`. The nodes of a failed patch
+/// may never have got into the DOM, or may have left it already.
+pub(crate) fn is_child(node: &Node, parent: &Node) -> bool {
+    node.parent_node().as_ref() == Some(parent)
+}
@@ -0,0 +1,146 @@
+//! Error boundaries, which catch the failures of the components within them.
+//!
+//! A component becomes an error boundary by returning a markup from
+//! `Render::error_fallback`. When a component within it fails to render or to
+//! patch the DOM, the failed render of the boundary is removed along with
+//! whatever the failed patch got into the DOM, & the fallback is shown in its
+//! place. A panic while rendering counts as a failure too, where panics unwind,
+//! which they do not in the browser. The error is then passed on to
+//! `Lifecycle::caught_error` along with the path of the failed component. The
+//! boundary keeps showing the fallback till its props change, when the render
+//! is tried again.
+//!
+//! # Example
+//! ```ignore
+//! #[component]
+//! struct Dashboard;
+//!
+//! impl Lifecycle for Dashboard {
+//!     fn caught_error(&self, error: CaughtError) {
+//!         log(&format!("{} failed", error.path.join(" > ")));
+//!     }
+//! }
+//!
+//! impl Render for Dashboard {
+//!     fn render(&self) -> Markup<Self> {
+//!         html! {
+//!             <Chart />
+//!         }
+//!     }
+//!
+//!     fn error_fallback(&self, _: &CaughtError) -> Option<Markup<Self>> {
+//!         Some(html! {
+//!             <p>"The chart could not be shown."</p>
+//!         })
+//!     }
+//! }
+//! ```
+use std::cell::RefCell;
+use wasm_bindgen::prelude::JsValue;
+
+/// An error caught by an error boundary.
+#[derive(Debug)]
+pub struct CaughtError {
+    /// The error thrown by the DOM.
+    pub error: JsValue,
+    /// The type names of the components from the child of the boundary till
+    /// the failed component.
+    pub path: Vec<&'static str>,
+}
+
+thread_local! {
+    /// The components the error propagated through, the failed one first.
+    static TRACE: RefCell<Vec<&'static str>> = const { RefCell::new(vec![]) };
+}
+
+/// Records that the error propagated through the `component`.
+pub(crate) fn trace(component: &'static str) {
+    TRACE.with(|trace| trace.borrow_mut().push(component));
+}
+
+/// The path of the components the error propagated through, from the
+/// outermost.
+pub(crate) fn path() -> Vec<&'static str> {
+    TRACE.with(|trace| trace.borrow().iter().rev().cloned().collect())
+}
+
+/// Clears the path, once the error is caught.
+pub(crate) fn clear_path() {
+    TRACE.with(|trace| trace.borrow_mut().clear());
+}
+
+/// Walks the components from the root with `walk`. The path of an error that
+/// is not caught within is cleared as it leaves the root, so that it does not
+/// carry over to the next error.
+pub(crate) fn from_root<R>(walk: impl FnOnce() -> Result<R, JsValue>) -> Result<R, JsValue> {
+    let walked = walk();
+    if walked.is_err() {
+        clear_path();
+    }
+    walked
+}
+
+/// Runs `f`, turning a panic into the message it panicked with where panics
+/// unwind, which they do not in the browser.
+#[cfg(all(not(target_arch = "wasm32"), panic = "unwind"))]
+pub(crate) fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(&*payload))
+}
+
+/// Runs `f`, turning a panic into the message it panicked with where panics
+/// unwind, which they do not in the browser.
+#[cfg(not(all(not(target_arch = "wasm32"), panic = "unwind")))]
+pub(crate) fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    Ok(f())
+}
+
+#[cfg(all(not(target_arch = "wasm32"), panic = "unwind"))]
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "The component panicked.".to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_trace_path_from_outermost() {
+        trace("app::Chart");
+        trace("app::Panel");
+        assert_eq!(path(), vec!["app::Panel", "app::Chart"]);
+        clear_path();
+        assert!(path().is_empty());
+    }
+
+    #[test]
+    fn should_clear_path_of_error_leaving_root() {
+        let walked: Result<(), JsValue> = from_root(|| {
+            trace("app::Chart");
+            Err(JsValue::NULL)
+        });
+        assert!(walked.is_err());
+        assert!(path().is_empty());
+    }
+
+    #[cfg(all(not(target_arch = "wasm32"), panic = "unwind"))]
+    #[test]
+    fn should_catch_panic_with_its_message() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+        assert_eq!(
+            catch_panic::<()>(|| panic!("no data")),
+            Err("no data".to_string())
+        );
+        assert_eq!(
+            catch_panic::<()>(|| panic!("no {}", "data")),
+            Err("no data".to_string())
+        );
+    }
+}
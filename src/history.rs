@@ -0,0 +1,272 @@
+//! Undo & redo history of the state of the components.
+//!
+//! A component records its history when declared with
+//! `#[component(history)]`, or `#[component(history = limit)]` to limit the
+//! number of undo steps. A snapshot of the state is recorded whenever
+//! `set_state` commits a change, which may then be undone with `undo()` &
+//! redone with `redo()`.
+use crate::component::Component;
+use std::{collections::VecDeque, mem};
+
+/// The number of undo steps kept by default.
+pub const DEFAULT_LIMIT: usize = 100;
+
+/// The past & future snapshots of a state.
+pub struct History<T> {
+    past: VecDeque<T>,
+    future: Vec<T>,
+    limit: usize,
+    transaction: Option<Transaction<T>>,
+}
+
+/// A group of changes which is undone as a single step.
+struct Transaction<T> {
+    /// The depth of the nested transactions.
+    depth: usize,
+    /// The snapshot before the first change within the transaction.
+    before: Option<T>,
+}
+
+impl<T> History<T> {
+    /// Creates an empty history which keeps at most `limit` undo steps.
+    pub fn new(limit: usize) -> History<T> {
+        History {
+            past: VecDeque::new(),
+            future: vec![],
+            limit,
+            transaction: None,
+        }
+    }
+
+    /// Records the snapshot of the state `before` a change. Any undone
+    /// snapshots are discarded, as they can no longer be redone.
+    ///
+    /// Within a transaction, only the snapshot before the first change is
+    /// recorded once the transaction is committed.
+    pub fn record(&mut self, before: T) {
+        if let Some(ref mut transaction) = self.transaction {
+            if transaction.before.is_none() {
+                transaction.before = Some(before);
+            }
+            return;
+        }
+
+        self.future.clear();
+        self.past.push_back(before);
+        self.truncate();
+    }
+
+    /// Restores the `current` state to the last recorded snapshot. Returns
+    /// whether there was anything to undo.
+    pub fn undo(&mut self, current: &mut T) -> bool {
+        if let Some(before) = self.past.pop_back() {
+            self.future.push(mem::replace(current, before));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Restores the `current` state to the last undone snapshot. Returns
+    /// whether there was anything to redo.
+    pub fn redo(&mut self, current: &mut T) -> bool {
+        if let Some(after) = self.future.pop() {
+            self.past.push_back(mem::replace(current, after));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Whether there is anything to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.past.is_empty()
+    }
+
+    /// Whether there is anything to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.future.is_empty()
+    }
+
+    /// Begins a transaction, within which all the changes are grouped into a
+    /// single undo step. Transactions may be nested.
+    pub fn begin(&mut self) {
+        match self.transaction {
+            Some(ref mut transaction) => transaction.depth += 1,
+            None => {
+                self.transaction = Some(Transaction {
+                    depth: 1,
+                    before: None,
+                })
+            }
+        }
+    }
+
+    /// Commits the transaction begun last. The changes are recorded once the
+    /// outermost transaction is committed.
+    pub fn commit(&mut self) {
+        let before = match self.transaction {
+            Some(ref mut transaction) if transaction.depth > 1 => {
+                transaction.depth -= 1;
+                return;
+            }
+            Some(_) => self.transaction.take().and_then(|t| t.before),
+            None => return,
+        };
+
+        if let Some(before) = before {
+            self.record(before);
+        }
+    }
+
+    /// Discards all the snapshots.
+    pub fn clear(&mut self) {
+        self.past.clear();
+        self.future.clear();
+    }
+
+    /// The maximum number of undo steps kept.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Sets the maximum number of undo steps kept, discarding the oldest
+    /// ones.
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        self.truncate();
+    }
+
+    fn truncate(&mut self) {
+        while self.past.len() > self.limit {
+            self.past.pop_front();
+        }
+    }
+}
+
+/// Undo & redo the state changes of a component. It is implemented for the
+/// components declared with `#[component(history)]`.
+pub trait Undo: Component {
+    /// Undoes the last change to the state. Returns whether there was
+    /// anything to undo.
+    fn undo(&self) -> bool {
+        self.status()
+            .is_some_and(|status| status.borrow_mut().undo())
+    }
+
+    /// Redoes the last undone change to the state. Returns whether there was
+    /// anything to redo.
+    fn redo(&self) -> bool {
+        self.status()
+            .is_some_and(|status| status.borrow_mut().redo())
+    }
+
+    /// Whether there is anything to undo.
+    fn can_undo(&self) -> bool {
+        self.status().is_some_and(|status| {
+            status.borrow().history().is_some_and(History::can_undo)
+        })
+    }
+
+    /// Whether there is anything to redo.
+    fn can_redo(&self) -> bool {
+        self.status().is_some_and(|status| {
+            status.borrow().history().is_some_and(History::can_redo)
+        })
+    }
+
+    /// Groups all the state changes made within `f` into a single undo step.
+    fn transaction(&self, f: impl FnOnce()) {
+        with_history(self, History::begin);
+        let _commit = Commit(self);
+        f();
+    }
+}
+
+/// Commits the transaction when dropped, so that it is committed even if the
+/// changes within it panic.
+struct Commit<'a, COMP: Component + ?Sized>(&'a COMP);
+
+impl<'a, COMP: Component + ?Sized> Drop for Commit<'a, COMP> {
+    fn drop(&mut self) {
+        with_history(self.0, History::commit);
+    }
+}
+
+/// Applies the `action` to the history of the component, if recorded.
+fn with_history<COMP: Component + ?Sized>(
+    component: &COMP,
+    action: fn(&mut History<COMP::State>),
+) {
+    if let Some(status) = component.status() {
+        if let Some(history) = status.borrow_mut().history_mut() {
+            action(history);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_undo_and_redo() {
+        let mut history = History::new(DEFAULT_LIMIT);
+        let mut state = 2;
+        history.record(1);
+
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 1);
+        assert!(!history.undo(&mut state));
+
+        assert!(history.redo(&mut state));
+        assert_eq!(state, 2);
+        assert!(!history.redo(&mut state));
+    }
+
+    #[test]
+    fn should_discard_future_on_record() {
+        let mut history = History::new(DEFAULT_LIMIT);
+        let mut state = 2;
+        history.record(1);
+        history.undo(&mut state);
+        assert!(history.can_redo());
+
+        history.record(1);
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn should_keep_history_within_limit() {
+        let mut history = History::new(2);
+        let mut state = 4;
+        history.record(1);
+        history.record(2);
+        history.record(3);
+
+        assert!(history.undo(&mut state));
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 2);
+        assert!(!history.can_undo());
+
+        history.set_limit(0);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn should_group_changes_in_transaction() {
+        let mut history = History::new(DEFAULT_LIMIT);
+        let mut state = 3;
+        history.begin();
+        history.record(1);
+        history.begin();
+        history.record(2);
+        history.commit();
+        assert!(!history.can_undo());
+        history.commit();
+
+        assert!(history.undo(&mut state));
+        assert_eq!(state, 1);
+        assert!(!history.can_undo());
+    }
+}
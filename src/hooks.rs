@@ -0,0 +1,521 @@
+//! Hooks to keep local state & run effects within the render of a component,
+//! which is meant for the function components.
+//!
+//! The hooks are stored per component instance, in the order of their calls.
+//! So, they are required to be called in the same order on every render of the
+//! component i.e. never within a condition or a loop, which is checked in the
+//! debug builds.
+//!
+//! # Example
+//! ```ignore
+//! #[component]
+//! fn Counter() -> Markup<Self> {
+//!     let (count, set_count) = use_state(|| 0);
+//!     use_effect(count, move || {
+//!         log(&format!("Clicked {} times", count));
+//!     });
+//!
+//!     html! {
+//!         <button @click={move |_, _| set_count.set(count + 1)}>{ count }</button>
+//!     }
+//! }
+//! ```
+use crate::MessageSender;
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    mem,
+    rc::Rc,
+};
+
+/// The cleanup of an effect, which is run before the effect is run again or
+/// when the component is destroyed.
+type CleanupFn = Box<dyn FnOnce()>;
+
+/// The cleanup of the last run of an effect, if any.
+type CleanupSlot = Rc<RefCell<Option<CleanupFn>>>;
+
+/// An effect to be run after the patches are applied to the DOM.
+struct PendingEffect {
+    cleanup: CleanupSlot,
+    effect: Box<dyn FnOnce() -> Option<CleanupFn>>,
+}
+
+/// The hooks of a component instance. They are created on the first hook
+/// call, so that the components which never call a hook go without them.
+pub(crate) struct Hooks {
+    slots: Vec<Box<dyn Any>>,
+    /// The names of the hooks in the order of their calls.
+    #[cfg(debug_assertions)]
+    kinds: Vec<&'static str>,
+    /// The index of the next hook to be called in a render.
+    cursor: usize,
+    is_first_render: bool,
+    /// Whether a state hook changed since the last render.
+    dirty: Rc<Cell<bool>>,
+    rx_sender: MessageSender,
+    effects: Vec<PendingEffect>,
+    cleanups: Vec<CleanupSlot>,
+}
+
+impl Hooks {
+    fn new(rx_sender: MessageSender, is_first_render: bool) -> Hooks {
+        Hooks {
+            slots: vec![],
+            #[cfg(debug_assertions)]
+            kinds: vec![],
+            cursor: 0,
+            is_first_render,
+            dirty: Rc::new(Cell::new(false)),
+            rx_sender,
+            effects: vec![],
+            cleanups: vec![],
+        }
+    }
+
+    /// Whether a state hook changed since the last render. The flag is reset.
+    pub(crate) fn take_dirty(&self) -> bool {
+        self.dirty.replace(false)
+    }
+
+    /// Claims the slot of the next hook called. Returns its index along with
+    /// whether it is yet to be filled, which it is on the first render.
+    fn claim(&mut self, kind: &'static str) -> (usize, bool) {
+        let index = self.cursor;
+        self.cursor += 1;
+
+        if index == self.slots.len() {
+            if !self.is_first_render {
+                panic!(
+                    "`{}` is called more times than in the previous render. Hooks must \
+                     be called in the same order on every render.",
+                    kind
+                );
+            }
+            self.slots.push(Box::new(()));
+            #[cfg(debug_assertions)]
+            self.kinds.push(kind);
+            return (index, true);
+        }
+
+        #[cfg(debug_assertions)]
+        {
+            if self.kinds[index] != kind {
+                panic!(
+                    "`{}` is called where `{}` was called in the previous render. Hooks \
+                     must be called in the same order on every render.",
+                    kind, self.kinds[index]
+                );
+            }
+        }
+        (index, false)
+    }
+
+    /// Fills the slot at the `index` with the value of the hook.
+    fn fill<T: 'static>(&mut self, index: usize, value: T) {
+        self.slots[index] = Box::new(value);
+    }
+
+    /// Gets the value of the hook in the slot at the `index`.
+    fn slot<T: 'static>(&mut self, index: usize, kind: &'static str) -> &mut T {
+        self.slots[index].downcast_mut().unwrap_or_else(|| {
+            panic!(
+                "`{}` is called with a different type than in the previous render. Hooks \
+                 must be called in the same order on every render.",
+                kind
+            )
+        })
+    }
+}
+
+/// The component being rendered.
+struct Rendering {
+    hooks: Option<Hooks>,
+    rx_sender: MessageSender,
+    is_first_render: bool,
+}
+
+thread_local! {
+    /// The component being rendered, if any.
+    static CURRENT: RefCell<Option<Rendering>> = const { RefCell::new(None) };
+}
+
+/// Restores the outer render & hands the hooks back to the component, even if
+/// the render panics.
+struct Restore<'a> {
+    previous: Option<Rendering>,
+    hooks: &'a mut Option<Hooks>,
+}
+
+impl Drop for Restore<'_> {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        let rendered = CURRENT.with(|current| current.replace(previous));
+        *self.hooks = rendered.and_then(|rendered| rendered.hooks);
+    }
+}
+
+/// Renders a component with its `hooks` available to the hook calls. The
+/// hooks are created on the first hook call, if there are none yet.
+pub(crate) fn render<R>(
+    hooks: &mut Option<Hooks>,
+    rx_sender: &MessageSender,
+    is_first_render: bool,
+    render: impl FnOnce() -> R,
+) -> R {
+    if let Some(ref mut hooks) = hooks {
+        hooks.cursor = 0;
+    }
+    let rendering = Rendering {
+        hooks: hooks.take(),
+        rx_sender: rx_sender.clone(),
+        is_first_render,
+    };
+    let previous = CURRENT.with(|current| current.replace(Some(rendering)));
+    let rendered = {
+        let _restore = Restore { previous, hooks };
+        render()
+    };
+
+    if let Some(ref mut hooks) = hooks {
+        if cfg!(debug_assertions) && hooks.cursor < hooks.slots.len() {
+            panic!(
+                "Fewer hooks are called than in the previous render. Hooks must be called \
+                 in the same order on every render."
+            );
+        }
+        hooks.is_first_render = false;
+    }
+    rendered
+}
+
+/// Runs the effects queued in the last render.
+pub(crate) fn run_effects(hooks: &mut Hooks) {
+    let effects = mem::take(&mut hooks.effects);
+    for PendingEffect { cleanup, effect } in effects {
+        let previous = cleanup.borrow_mut().take();
+        if let Some(previous) = previous {
+            previous();
+        }
+        let next = effect();
+        *cleanup.borrow_mut() = next;
+    }
+}
+
+/// Runs the cleanups of all the effects, when the component is destroyed.
+pub(crate) fn clean_up(hooks: &Hooks) {
+    for cleanup in &hooks.cleanups {
+        let cleanup = cleanup.borrow_mut().take();
+        if let Some(cleanup) = cleanup {
+            cleanup();
+        }
+    }
+}
+
+/// Calls `f` with the hooks of the component being rendered, creating them on
+/// the first call. No user code is to be run within `f`, as the hooks are
+/// borrowed all the while.
+fn with_hooks<R>(f: impl FnOnce(&mut Hooks) -> R) -> R {
+    CURRENT.with(|current| {
+        let mut current = current.borrow_mut();
+        let rendering = current
+            .as_mut()
+            .expect("Hooks can only be called while rendering a component.");
+        let Rendering {
+            ref mut hooks,
+            ref rx_sender,
+            is_first_render,
+        } = *rendering;
+        let hooks = hooks.get_or_insert_with(|| Hooks::new(rx_sender.clone(), is_first_render));
+        f(hooks)
+    })
+}
+
+/// The setter of a state hook, which re-renders the component on change.
+pub struct UseState<T> {
+    value: Rc<RefCell<T>>,
+    dirty: Rc<Cell<bool>>,
+    rx_sender: MessageSender,
+}
+
+impl<T> Clone for UseState<T> {
+    fn clone(&self) -> Self {
+        UseState {
+            value: self.value.clone(),
+            dirty: self.dirty.clone(),
+            rx_sender: self.rx_sender.clone(),
+        }
+    }
+}
+
+impl<T> UseState<T> {
+    /// Sets the state to the `value`.
+    pub fn set(&self, value: T) {
+        *self.value.borrow_mut() = value;
+        self.changed();
+    }
+
+    /// Mutates the state with the `mutator`.
+    pub fn update(&self, mutator: impl FnOnce(&mut T)) {
+        mutator(&mut self.value.borrow_mut());
+        self.changed();
+    }
+
+    fn changed(&self) {
+        self.dirty.set(true);
+        self.rx_sender.do_react();
+    }
+}
+
+/// Keeps a local state, initialized with `init` on the first render. Returns
+/// the current value along with a setter to change it.
+pub fn use_state<T: Clone + 'static>(init: impl FnOnce() -> T) -> (T, UseState<T>) {
+    let (index, is_new) = with_hooks(|hooks| hooks.claim("use_state"));
+    if is_new {
+        let value = Rc::new(RefCell::new(init()));
+        with_hooks(|hooks| {
+            let setter = UseState {
+                value,
+                dirty: hooks.dirty.clone(),
+                rx_sender: hooks.rx_sender.clone(),
+            };
+            hooks.fill(index, setter);
+        });
+    }
+    let setter = with_hooks(|hooks| hooks.slot::<UseState<T>>(index, "use_state").clone());
+    let value = setter.value.borrow().clone();
+    (value, setter)
+}
+
+/// The value returned by an effect, which may clean up after it.
+///
+/// It is implemented for `()`, when there is nothing to clean up, and for
+/// closures which clean up.
+pub trait Cleanup: 'static {
+    /// Converts into the cleanup closure, if any.
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>>;
+}
+
+impl Cleanup for () {
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>> {
+        None
+    }
+}
+
+impl<F: FnOnce() + 'static> Cleanup for F {
+    fn into_cleanup(self) -> Option<Box<dyn FnOnce()>> {
+        Some(Box::new(self))
+    }
+}
+
+struct EffectSlot<D> {
+    deps: D,
+    cleanup: CleanupSlot,
+}
+
+/// Runs the `effect` after the patches of the render are applied to the DOM,
+/// on the first render and then whenever the `deps` change. Pass `()` as the
+/// `deps` to run it only once.
+///
+/// The effect may return a closure to clean up after it, which is run before
+/// the effect is run again and when the component is destroyed.
+pub fn use_effect<D, C>(deps: D, effect: impl FnOnce() -> C + 'static)
+where
+    D: PartialEq + 'static,
+    C: Cleanup,
+{
+    with_hooks(|hooks| {
+        let (index, is_new) = hooks.claim("use_effect");
+        let cleanup = if is_new {
+            let cleanup = Rc::new(RefCell::new(None));
+            hooks.cleanups.push(cleanup.clone());
+            hooks.fill(
+                index,
+                EffectSlot {
+                    deps,
+                    cleanup: cleanup.clone(),
+                },
+            );
+            Some(cleanup)
+        } else {
+            let slot = hooks.slot::<EffectSlot<D>>(index, "use_effect");
+            if slot.deps != deps {
+                slot.deps = deps;
+                Some(slot.cleanup.clone())
+            } else {
+                None
+            }
+        };
+
+        if let Some(cleanup) = cleanup {
+            hooks.effects.push(PendingEffect {
+                cleanup,
+                effect: Box::new(move || effect().into_cleanup()),
+            });
+        }
+    });
+}
+
+struct MemoSlot<D, T> {
+    deps: D,
+    value: Rc<T>,
+}
+
+/// Computes a value on the first render and then only whenever the `deps`
+/// change.
+pub fn use_memo<D, T>(deps: D, compute: impl FnOnce() -> T) -> Rc<T>
+where
+    D: PartialEq + 'static,
+    T: 'static,
+{
+    let (index, is_new) = with_hooks(|hooks| hooks.claim("use_memo"));
+    if !is_new {
+        let memoized = with_hooks(|hooks| {
+            let slot = hooks.slot::<MemoSlot<D, T>>(index, "use_memo");
+            if slot.deps == deps {
+                Some(slot.value.clone())
+            } else {
+                None
+            }
+        });
+        if let Some(value) = memoized {
+            return value;
+        }
+    }
+
+    let value = Rc::new(compute());
+    with_hooks(|hooks| {
+        hooks.fill(
+            index,
+            MemoSlot {
+                deps,
+                value: value.clone(),
+            },
+        )
+    });
+    value
+}
+
+/// Keeps a mutable value, initialized with `init` on the first render, which
+/// does not re-render the component when changed.
+pub fn use_ref<T: 'static>(init: impl FnOnce() -> T) -> Rc<RefCell<T>> {
+    let (index, is_new) = with_hooks(|hooks| hooks.claim("use_ref"));
+    if is_new {
+        let value = Rc::new(RefCell::new(init()));
+        with_hooks(|hooks| hooks.fill(index, value.clone()));
+        return value;
+    }
+    with_hooks(|hooks| hooks.slot::<Rc<RefCell<T>>>(index, "use_ref").clone())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    /// A component instance, as far as its hooks go.
+    struct Instance {
+        hooks: Option<Hooks>,
+        rx_sender: MessageSender,
+        is_rendered: bool,
+    }
+
+    impl Instance {
+        fn new() -> Instance {
+            Instance {
+                hooks: None,
+                rx_sender: crate::message_sender(),
+                is_rendered: false,
+            }
+        }
+
+        fn render<R>(&mut self, f: impl FnOnce() -> R) -> R {
+            let is_first_render = !self.is_rendered;
+            self.is_rendered = true;
+            render(&mut self.hooks, &self.rx_sender, is_first_render, f)
+        }
+
+        fn hooks(&mut self) -> &mut Hooks {
+            self.hooks.as_mut().unwrap()
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn should_keep_state_across_renders() {
+        let mut instance = Instance::new();
+        let (count, setter) = instance.render(|| use_state(|| 1));
+        assert_eq!(count, 1);
+
+        setter.set(2);
+        assert!(instance.hooks().take_dirty());
+        let (count, _) = instance.render(|| use_state(|| 1));
+        assert_eq!(count, 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_memoize_until_deps_change() {
+        let mut instance = Instance::new();
+        let computed = Rc::new(Cell::new(0));
+        let mut memo = |deps: i32| {
+            let computed = computed.clone();
+            instance.render(|| {
+                use_memo(deps, || {
+                    computed.set(computed.get() + 1);
+                    deps * 2
+                })
+            })
+        };
+
+        assert_eq!(*memo(1), 2);
+        assert_eq!(*memo(1), 2);
+        assert_eq!(computed.get(), 1);
+        assert_eq!(*memo(2), 4);
+        assert_eq!(computed.get(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_run_effects_and_clean_up() {
+        let mut instance = Instance::new();
+        let log = Rc::new(RefCell::new(vec![]));
+        let mut effect = |deps: i32| {
+            let log = log.clone();
+            instance.render(|| {
+                use_effect(deps, move || {
+                    log.borrow_mut().push(format!("run {}", deps));
+                    move || log.borrow_mut().push(format!("clean {}", deps))
+                })
+            });
+            run_effects(instance.hooks());
+        };
+
+        effect(1);
+        effect(1);
+        effect(2);
+        clean_up(instance.hooks());
+        assert_eq!(*log.borrow(), vec!["run 1", "clean 1", "run 2", "clean 2"]);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_keep_refs_across_renders() {
+        let mut instance = Instance::new();
+        let value = instance.render(|| use_ref(|| 1));
+        *value.borrow_mut() = 2;
+        let value = instance.render(|| use_ref(|| 1));
+        assert_eq!(*value.borrow(), 2);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_create_hooks_on_first_call() {
+        let mut instance = Instance::new();
+        instance.render(|| ());
+        assert!(instance.hooks.is_none());
+    }
+
+    #[wasm_bindgen_test]
+    #[should_panic(expected = "`use_ref` is called more times than in the previous render.")]
+    fn should_not_call_hooks_after_first_render_without_hooks() {
+        let mut instance = Instance::new();
+        instance.render(|| ());
+        instance.render(|| use_ref(|| 1));
+    }
+}
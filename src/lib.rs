@@ -58,7 +58,18 @@ use wasm_bindgen::{prelude::*, JsCast};
 use web_sys::{window, Element, MessageChannel, MessagePort};
 
 pub mod component;
+pub mod custom_element;
+#[cfg(feature = "devtools")]
+pub mod devtools;
 mod dom;
+pub mod error;
+pub mod history;
+pub mod hooks;
+#[cfg(feature = "profiler")]
+pub mod profiler;
+#[cfg(feature = "persist")]
+pub mod storage;
+pub mod suspense;
 pub mod vdom;
 
 /// A VDOM Markup which is generated by using `html!` macro.
@@ -67,8 +78,12 @@ pub type Markup<RCTX> = vdom::VNode<RCTX>;
 /// Things you'll require to build the next great App. Just glob import the
 /// prelude and start building your app.
 pub mod prelude {
-    pub use crate::component::{Component, Lifecycle, Render, SetState, StateSetter};
-    pub use crate::{App, Markup};
+    pub use crate::component::{Component, Lifecycle, Render, SetState, StateSetter, Update};
+    pub use crate::hooks::{use_effect, use_memo, use_ref, use_state};
+    pub use crate::{
+        custom_element::CustomElement, error::CaughtError, history::Undo, suspense::Loading,
+        vdom::transition::Transition, App, Markup,
+    };
     pub use ruukh_codegen::*;
 }
 
@@ -76,6 +91,8 @@ pub mod prelude {
 pub mod reexports {
     pub use fnv::FnvBuildHasher;
     pub use indexmap::IndexMap;
+    pub use wasm_bindgen::JsValue;
+    pub use web_sys::EventTarget;
 }
 
 /// The main entry point to use your component and run it on the browser.
@@ -145,23 +162,42 @@ where
     /// # }
     /// App::<MyApp>::new().mount("app");
     /// ```
-    pub fn mount(mut self, element: impl AppMount) {
+    pub fn mount(self, element: impl AppMount) {
         let parent = element.app_mount();
         let (receiver, sender) = app_message_channel();
 
         // Every component requires a render context, so provided a void context.
         let root_parent = Rc::new(RefCell::new(()));
 
+        let manager = Rc::new(RefCell::new(self.manager));
+
         // The first render
-        self.manager
-            .render_walk(parent.as_ref(), None, root_parent.clone(), sender.clone())
-            .unwrap();
+        sender.enter(|| {
+            error::from_root(|| {
+                manager.borrow_mut().render_walk(
+                    parent.as_ref(),
+                    None,
+                    root_parent.clone(),
+                    sender.clone(),
+                )
+            })
+        }).unwrap();
+
+        #[cfg(feature = "devtools")]
+        devtools::register(&manager);
 
         // Rerender when it receives update messages.
         receiver.react_on_message(move || {
-            self.manager
-                .render_walk(parent.as_ref(), None, root_parent.clone(), sender.clone())
-                .unwrap();
+            sender.enter(|| {
+                error::from_root(|| {
+                    manager.borrow_mut().render_walk(
+                        parent.as_ref(),
+                        None,
+                        root_parent.clone(),
+                        sender.clone(),
+                    )
+                })
+            }).unwrap();
         });
     }
 }
@@ -182,56 +218,96 @@ where
 fn app_message_channel() -> (MessageReceiver, MessageSender) {
     let msg_channel = MessageChannel::new().unwrap();
     let is_queued = Rc::new(RefCell::new(false));
+    let after_render = Rc::new(RefCell::new(vec![]));
     (
         MessageReceiver {
             port: msg_channel.port2(),
             is_queued: is_queued.clone(),
+            after_render: after_render.clone(),
         },
         MessageSender {
             port: msg_channel.port1(),
             is_queued,
+            after_render,
         },
     )
 }
 
+/// The callbacks to be invoked once the app is rendered.
+type Callbacks = Shared<Vec<Box<dyn FnOnce()>>>;
+
 /// The receiving end of the message port which notifies the app for any state
 /// changes.
 pub struct MessageReceiver {
     port: MessagePort,
     is_queued: Shared<bool>,
+    after_render: Callbacks,
 }
 
 impl MessageReceiver {
     /// Invokes the handler, when it receives a message.
-    fn react_on_message(self, mut handler: impl FnMut() + 'static) {
+    fn react_on_message(self, handler: impl FnMut() + 'static) {
+        // Leak the closure so that the app lives on for 'static lifetimes.
+        self.listen(handler).forget();
+    }
+
+    /// Invokes the handler, when it receives a message, as long as the
+    /// returned closure is alive.
+    fn listen(self, mut handler: impl FnMut() + 'static) -> MessageListener {
         let is_queued = self.is_queued.clone();
-        let closure: Closure<dyn FnMut(JsValue)> = Closure::wrap(Box::new(move |_| {
+        let after_render = self.after_render.clone();
+        let closure: MessageListener = Closure::wrap(Box::new(move |_| {
             handler();
 
             // Unblock the queue.
             *is_queued.borrow_mut() = false;
+
+            // Taken out first, as the callbacks may queue more of them.
+            let callbacks: Vec<_> = after_render.borrow_mut().drain(..).collect();
+            for callback in callbacks {
+                callback();
+            }
         }));
         self.port
             .set_onmessage(Some(closure.as_ref().unchecked_ref()));
-
-        // Leak the closure so that the app lives on for 'static lifetimes.
-        closure.forget();
+        closure
     }
 }
 
+/// The closure which listens to the update messages.
+type MessageListener = Closure<dyn FnMut(JsValue)>;
+
 /// MessageSender is responsible to message the App about state changes.
 #[derive(Clone)]
 struct MessageSender {
     port: MessagePort,
     is_queued: Shared<bool>,
+    after_render: Callbacks,
 }
 
 impl MessageSender {
     /// Sends an update message to the App.
     ///
     /// The components need to call this method when they desire the app to
-    /// be notified of state changes.
+    /// be notified of state changes. Within a `batch`, the message is only
+    /// sent once the batch is done.
     fn do_react(&self) {
+        let is_batched = BATCH.with(|batch| match *batch.borrow_mut() {
+            Some(ref mut pending) => {
+                if !pending
+                    .iter()
+                    .any(|sender| Rc::ptr_eq(&sender.is_queued, &self.is_queued))
+                {
+                    pending.push(self.clone());
+                }
+                true
+            }
+            None => false,
+        });
+        if is_batched {
+            return;
+        }
+
         let is_queued = *self.is_queued.borrow();
         if !is_queued {
             *self.is_queued.borrow_mut() = true;
@@ -241,6 +317,89 @@ impl MessageSender {
                 .expect("Could not send the message");
         }
     }
+
+    /// Invokes the callback once the App is rendered next, after its patches
+    /// are applied to the DOM.
+    fn next_tick(&self, callback: impl FnOnce() + 'static) {
+        self.after_render.borrow_mut().push(Box::new(callback));
+        self.do_react();
+    }
+
+    /// Runs `f` on behalf of the App, so that the components without a status
+    /// can still reach the App from within it.
+    fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _outer = AppGuard(CURRENT_APP.with(|app| app.borrow_mut().replace(self.clone())));
+        f()
+    }
+}
+
+/// Restores the outer App when dropped, so that it is restored even if the
+/// function run on behalf of the App panics.
+struct AppGuard(Option<MessageSender>);
+
+impl Drop for AppGuard {
+    fn drop(&mut self) {
+        let outer = self.0.take();
+        CURRENT_APP.with(|app| *app.borrow_mut() = outer);
+    }
+}
+
+/// Invokes the callback once the App being rendered, or whose event is being
+/// handled, is rendered next. Outside of any App, the callback is dropped with
+/// a warning.
+pub(crate) fn next_tick(callback: impl FnOnce() + 'static) {
+    match CURRENT_APP.with(|app| app.borrow().clone()) {
+        Some(app) => app.next_tick(callback),
+        None => web_sys::console::warn_1(&JsValue::from_str(
+            "The `next_tick` callback is dropped, as it is not invoked within an App.",
+        )),
+    }
+}
+
+thread_local! {
+    /// The Apps to be messaged once the outermost batch is done, if within a
+    /// batch.
+    static BATCH: RefCell<Option<Vec<MessageSender>>> = const { RefCell::new(None) };
+
+    /// The App being rendered, or whose event is being handled.
+    static CURRENT_APP: RefCell<Option<MessageSender>> = const { RefCell::new(None) };
+}
+
+/// Ends the outermost batch when dropped, so that it ends even if the batched
+/// function panics.
+struct BatchGuard;
+
+impl Drop for BatchGuard {
+    fn drop(&mut self) {
+        let pending = BATCH.with(|batch| batch.borrow_mut().take());
+        for sender in pending.unwrap_or_default() {
+            sender.do_react();
+        }
+    }
+}
+
+/// Groups the state changes made within `f`, across any number of components,
+/// so that the apps are messaged about them only after `f` returns.
+///
+/// # Example
+/// ```ignore
+/// ruukh::batch(|| {
+///     list.set_state(|state| state.items.clear());
+///     counter.set_state(|state| state.count = 0);
+/// });
+/// ```
+pub fn batch<T>(f: impl FnOnce() -> T) -> T {
+    let _outermost = BATCH.with(|batch| {
+        let mut batch = batch.borrow_mut();
+        if batch.is_none() {
+            *batch = Some(vec![]);
+            Some(BatchGuard)
+        } else {
+            None
+        }
+    });
+
+    f()
 }
 
 /// A Shared Value.
@@ -288,3 +447,34 @@ impl AppMount for String {
 fn message_sender() -> MessageSender {
     app_message_channel().1
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[wasm_bindgen_test]
+    fn should_message_once_the_batch_is_done() {
+        let sender = message_sender();
+        batch(|| {
+            sender.do_react();
+            batch(|| sender.do_react());
+            assert!(!*sender.is_queued.borrow());
+        });
+        assert!(*sender.is_queued.borrow());
+    }
+
+    #[test]
+    fn should_end_the_batch_when_it_panics() {
+        let panicked = std::panic::catch_unwind(|| batch(|| panic!("Failed to batch.")));
+        assert!(panicked.is_err());
+        assert!(BATCH.with(|batch| batch.borrow().is_none()));
+    }
+
+    #[wasm_bindgen_test]
+    fn should_queue_callbacks_for_next_tick() {
+        let sender = message_sender();
+        sender.next_tick(|| {});
+        assert_eq!(sender.after_render.borrow().len(), 1);
+        assert!(*sender.is_queued.borrow());
+    }
+}
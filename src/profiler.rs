@@ -0,0 +1,297 @@
+//! Profiler of the component renders.
+//!
+//! Enabled with the `profiler` feature, every render of a component is
+//! recorded along with the time taken to render & patch it, the cause of the
+//! render and the number of DOM operations issued by the patch. The time is
+//! in milliseconds, as given by `performance.now()`.
+//!
+//! The renders may also be written out as `performance.mark` & `measure`
+//! entries with `set_performance_entries(true)`, to be viewed in the
+//! performance timeline of the browser.
+use std::{cell::RefCell, collections::VecDeque};
+
+/// The number of records kept by default.
+pub const DEFAULT_LIMIT: usize = 1000;
+
+/// The cause of a render.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cause {
+    /// The component is rendered for the first time.
+    Mount,
+    /// The props of the component changed.
+    Props,
+    /// The state of the component changed.
+    State,
+    /// Both the props & state of the component changed.
+    PropsAndState,
+    /// The component is forced to re-render with `force_update`.
+    Forced,
+}
+
+impl Cause {
+    pub(crate) fn new(forced: bool, props_changed: bool, state_changed: bool) -> Cause {
+        match (forced, props_changed, state_changed) {
+            (true, _, _) => Cause::Forced,
+            (_, true, true) => Cause::PropsAndState,
+            (_, true, false) => Cause::Props,
+            _ => Cause::State,
+        }
+    }
+}
+
+/// A render of a component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    /// The type name of the component.
+    pub component: &'static str,
+    /// The cause of the render.
+    pub cause: Cause,
+    /// The time taken by the `render` of the component.
+    pub render_time: f64,
+    /// The time taken to patch the DOM with the render.
+    ///
+    /// It covers all the elements & texts in the render along with the removal
+    /// of the components no longer rendered. The components within are
+    /// rendered after the patch, and are recorded on their own.
+    pub patch_time: f64,
+    /// The number of DOM operations issued by the patch, covering the same as
+    /// `patch_time`.
+    pub dom_ops: usize,
+}
+
+struct Profiler {
+    records: VecDeque<Record>,
+    limit: usize,
+    performance_entries: bool,
+    dom_ops: usize,
+    /// The number of profiles started, which tells their marks apart.
+    profiles: u64,
+}
+
+thread_local! {
+    static PROFILER: RefCell<Profiler> = RefCell::new(Profiler {
+        records: VecDeque::new(),
+        limit: DEFAULT_LIMIT,
+        performance_entries: false,
+        dom_ops: 0,
+        profiles: 0,
+    });
+}
+
+/// The renders recorded so far, the oldest first.
+pub fn records() -> Vec<Record> {
+    PROFILER.with(|p| p.borrow().records.iter().cloned().collect())
+}
+
+/// Takes out the renders recorded so far, the oldest first.
+pub fn take_records() -> Vec<Record> {
+    PROFILER.with(|p| p.borrow_mut().records.drain(..).collect())
+}
+
+/// Discards the renders recorded so far.
+pub fn clear() {
+    PROFILER.with(|p| p.borrow_mut().records.clear());
+}
+
+/// Sets the maximum number of records kept, discarding the oldest ones.
+pub fn set_limit(limit: usize) {
+    PROFILER.with(|p| {
+        let mut p = p.borrow_mut();
+        p.limit = limit;
+        p.truncate();
+    });
+}
+
+/// Sets whether the renders are written out as `performance.mark` &
+/// `performance.measure` entries.
+pub fn set_performance_entries(enabled: bool) {
+    PROFILER.with(|p| p.borrow_mut().performance_entries = enabled);
+}
+
+impl Profiler {
+    fn truncate(&mut self) {
+        while self.records.len() > self.limit {
+            self.records.pop_front();
+        }
+    }
+}
+
+/// Counts a DOM operation issued.
+pub(crate) fn count_dom_op() {
+    PROFILER.with(|p| p.borrow_mut().dom_ops += 1);
+}
+
+fn dom_ops() -> usize {
+    PROFILER.with(|p| p.borrow().dom_ops)
+}
+
+fn performance_entries() -> bool {
+    PROFILER.with(|p| p.borrow().performance_entries)
+}
+
+/// The sequence number of the next profile.
+fn next_profile() -> u64 {
+    PROFILER.with(|p| {
+        let mut p = p.borrow_mut();
+        p.profiles += 1;
+        p.profiles
+    })
+}
+
+/// Profiles a render of a component, from the start of its render till the
+/// end of its patch.
+pub(crate) struct Profile {
+    component: &'static str,
+    sequence: u64,
+    cause: Cause,
+    start: f64,
+    rendered: f64,
+    dom_ops: usize,
+}
+
+impl Profile {
+    /// Starts profiling a render of the `component`.
+    pub(crate) fn start(component: &'static str, cause: Cause) -> Profile {
+        let mut profile = Profile {
+            component,
+            sequence: next_profile(),
+            cause,
+            start: 0.0,
+            rendered: 0.0,
+            dom_ops: dom_ops(),
+        };
+        profile.mark("start");
+        profile.start = now();
+        profile.rendered = profile.start;
+        profile
+    }
+
+    /// Marks the end of the render & the start of the patch.
+    pub(crate) fn rendered(&mut self) {
+        self.rendered = now();
+        self.mark("rendered");
+    }
+
+    /// Marks the end of the patch & records the render.
+    pub(crate) fn finish(self) {
+        let end = now();
+        self.mark("patched");
+        self.measure("render", "start", "rendered");
+        self.measure("patch", "rendered", "patched");
+
+        let record = Record {
+            component: self.component,
+            cause: self.cause,
+            render_time: self.rendered - self.start,
+            patch_time: end - self.rendered,
+            dom_ops: dom_ops() - self.dom_ops,
+        };
+        PROFILER.with(|p| {
+            let mut p = p.borrow_mut();
+            p.records.push_back(record);
+            p.truncate();
+        });
+    }
+
+    /// The name of a mark, which is unique to the profile so that the
+    /// measures do not pick the marks of another render of the component.
+    fn mark_name(&self, point: &str) -> String {
+        format!("ruukh:{}:{}:{}", self.component, self.sequence, point)
+    }
+
+    fn mark(&self, point: &str) {
+        if !performance_entries() {
+            return;
+        }
+        if let Some(performance) = performance() {
+            let _ = performance.mark(&self.mark_name(point));
+        }
+    }
+
+    fn measure(&self, phase: &str, start: &str, end: &str) {
+        if !performance_entries() {
+            return;
+        }
+        if let Some(performance) = performance() {
+            let _ = performance.measure_with_start_mark_and_end_mark(
+                &format!("{} {}", self.component, phase),
+                &self.mark_name(start),
+                &self.mark_name(end),
+            );
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now() -> f64 {
+    performance().map_or(0.0, |performance| performance.now())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now() -> f64 {
+    use std::time::Instant;
+
+    thread_local! {
+        static ORIGIN: Instant = Instant::now();
+    }
+    ORIGIN.with(|origin| {
+        let elapsed = origin.elapsed();
+        elapsed.as_secs() as f64 * 1000.0 + f64::from(elapsed.subsec_nanos()) / 1_000_000.0
+    })
+}
+
+fn performance() -> Option<web_sys::Performance> {
+    web_sys::window()?.performance()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_record_renders() {
+        clear();
+        let mut profile = Profile::start("app::Counter", Cause::Mount);
+        profile.rendered();
+        count_dom_op();
+        count_dom_op();
+        profile.finish();
+
+        let taken = take_records();
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].component, "app::Counter");
+        assert_eq!(taken[0].cause, Cause::Mount);
+        assert_eq!(taken[0].dom_ops, 2);
+        assert!(taken[0].render_time >= 0.0);
+        assert!(taken[0].patch_time >= 0.0);
+        assert!(records().is_empty());
+    }
+
+    #[test]
+    fn should_keep_records_within_limit() {
+        clear();
+        set_limit(2);
+        for _ in 0..3 {
+            Profile::start("app::Counter", Cause::State).finish();
+        }
+        assert_eq!(records().len(), 2);
+
+        set_limit(DEFAULT_LIMIT);
+        clear();
+    }
+
+    #[test]
+    fn should_name_marks_per_profile() {
+        let first = Profile::start("app::Counter", Cause::Mount);
+        let second = Profile::start("app::Counter", Cause::State);
+        assert_ne!(first.mark_name("start"), second.mark_name("start"));
+    }
+
+    #[test]
+    fn should_tell_cause_from_flags() {
+        assert_eq!(Cause::new(false, true, true), Cause::PropsAndState);
+        assert_eq!(Cause::new(false, true, false), Cause::Props);
+        assert_eq!(Cause::new(false, false, true), Cause::State);
+        assert_eq!(Cause::new(true, true, true), Cause::Forced);
+    }
+}
@@ -0,0 +1,178 @@
+//! Storage to persist the state of the components across reloads.
+//!
+//! A state field marked with `#[state(persist = "key")]` is loaded from the
+//! storage when the component is created and saved to it whenever it changes.
+//! The values are stored as JSON, so the field type is required to implement
+//! `Serialize` & `Deserialize`.
+//!
+//! The `localStorage` is used by default on the Web, whereas an in-memory
+//! storage is used elsewhere, like in the native tests. Use `set_storage` to
+//! switch the storage for the whole app.
+use serde::{de::DeserializeOwned, Serialize};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// A key-value storage of strings, like the Web Storage.
+pub trait Storage {
+    /// Gets the value stored for the `key`.
+    fn get_item(&self, key: &str) -> Option<String>;
+
+    /// Stores the `value` for the `key`.
+    fn set_item(&self, key: &str, value: &str);
+
+    /// Removes the value stored for the `key`.
+    fn remove_item(&self, key: &str);
+}
+
+/// The `window.localStorage`, which persists across browser sessions.
+#[derive(Debug, Default)]
+pub struct LocalStorage;
+
+/// The `window.sessionStorage`, which persists as long as the page session.
+#[derive(Debug, Default)]
+pub struct SessionStorage;
+
+/// A storage which is lost once the app is gone. Useful in tests.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    items: RefCell<HashMap<String, String>>,
+}
+
+impl MemoryStorage {
+    /// Creates an empty in-memory storage.
+    pub fn new() -> MemoryStorage {
+        Default::default()
+    }
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+fn session_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.session_storage().ok()?
+}
+
+// The Web Storage may be unavailable or full, in which case the state simply
+// does not persist.
+
+impl Storage for LocalStorage {
+    fn get_item(&self, key: &str) -> Option<String> {
+        local_storage()?.get_item(key).ok()?
+    }
+
+    fn set_item(&self, key: &str, value: &str) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.set_item(key, value);
+        }
+    }
+
+    fn remove_item(&self, key: &str) {
+        if let Some(storage) = local_storage() {
+            let _ = storage.remove_item(key);
+        }
+    }
+}
+
+impl Storage for SessionStorage {
+    fn get_item(&self, key: &str) -> Option<String> {
+        session_storage()?.get_item(key).ok()?
+    }
+
+    fn set_item(&self, key: &str, value: &str) {
+        if let Some(storage) = session_storage() {
+            let _ = storage.set_item(key, value);
+        }
+    }
+
+    fn remove_item(&self, key: &str) {
+        if let Some(storage) = session_storage() {
+            let _ = storage.remove_item(key);
+        }
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get_item(&self, key: &str) -> Option<String> {
+        self.items.borrow().get(key).cloned()
+    }
+
+    fn set_item(&self, key: &str, value: &str) {
+        self.items
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+    }
+
+    fn remove_item(&self, key: &str) {
+        self.items.borrow_mut().remove(key);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn default_storage() -> Rc<dyn Storage> {
+    Rc::new(LocalStorage)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn default_storage() -> Rc<dyn Storage> {
+    Rc::new(MemoryStorage::new())
+}
+
+thread_local! {
+    static STORAGE: RefCell<Rc<dyn Storage>> = RefCell::new(default_storage());
+}
+
+/// Sets the storage used to persist the state of all the components.
+pub fn set_storage(storage: impl Storage + 'static) {
+    STORAGE.with(|s| *s.borrow_mut() = Rc::new(storage));
+}
+
+fn storage() -> Rc<dyn Storage> {
+    STORAGE.with(|s| s.borrow().clone())
+}
+
+/// Loads the value stored for the `key`. Returns `None` if there is none or
+/// it could not be deserialized.
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let value = storage().get_item(key)?;
+    serde_json::from_str(&value).ok()
+}
+
+/// Saves the `value` for the `key`.
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    if let Ok(value) = serde_json::to_string(value) {
+        storage().set_item(key, &value);
+    }
+}
+
+/// Removes the value stored for the `key`.
+pub fn remove(key: &str) {
+    storage().remove_item(key);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_store_in_memory() {
+        let storage = MemoryStorage::new();
+        storage.set_item("key", "value");
+        assert_eq!(storage.get_item("key"), Some("value".to_string()));
+
+        storage.remove_item("key");
+        assert_eq!(storage.get_item("key"), None);
+    }
+
+    #[test]
+    fn should_save_and_load_values() {
+        set_storage(MemoryStorage::new());
+        assert_eq!(load::<Vec<i32>>("numbers"), None);
+
+        save("numbers", &vec![1, 2, 3]);
+        assert_eq!(load::<Vec<i32>>("numbers"), Some(vec![1, 2, 3]));
+        assert_eq!(load::<String>("numbers"), None);
+
+        remove("numbers");
+        assert_eq!(load::<Vec<i32>>("numbers"), None);
+    }
+}
@@ -0,0 +1,128 @@
+//! Components which render only after their data is loaded, & the suspense
+//! boundaries which show a fallback while they load.
+//!
+//! A component loads its data by returning a future from `Lifecycle::load`,
+//! which is started once the component is created. The component is suspended
+//! till the future resolves, and is rendered for the first time after that.
+//! The data is loaded only once, & not again when the props change.
+//!
+//! A component becomes a suspense boundary by returning a markup from
+//! `Render::fallback`. While any of the components below it are loading, its
+//! render is kept aside & the fallback is shown in its place. Once all of them
+//! are loaded, the render is patched back in over the fallback, without
+//! remounting the components which already were.
+//!
+//! # Example
+//! ```ignore
+//! #[component]
+//! struct Profile {
+//!     id: u32,
+//!     #[state]
+//!     user: Option<User>,
+//! }
+//!
+//! impl Lifecycle for Profile {
+//!     fn load(&self) -> Option<Loading> {
+//!         let setter = self.state_setter();
+//!         Some(suspense::load(fetch_user(self.id), move |user| {
+//!             setter.set_state(|state| state.user = Some(user.clone()));
+//!         }))
+//!     }
+//! }
+//!
+//! #[component]
+//! #[derive(Lifecycle)]
+//! struct Page;
+//!
+//! impl Render for Page {
+//!     fn render(&self) -> Markup<Self> {
+//!         html! {
+//!             <Profile id={1} />
+//!         }
+//!     }
+//!
+//!     fn fallback(&self) -> Option<Markup<Self>> {
+//!         Some(html! {
+//!             <p>"Loading..."</p>
+//!         })
+//!     }
+//! }
+//! ```
+use crate::MessageSender;
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+};
+
+/// The load of a component, which it is suspended on.
+pub type Loading = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Loads the data with the `future` & passes it on to `then`, which may set it
+/// onto the state of the component.
+pub fn load<T>(
+    future: impl Future<Output = T> + 'static,
+    then: impl FnOnce(T) + 'static,
+) -> Loading {
+    Box::pin(async move { then(future.await) })
+}
+
+/// The number of loads pending below a suspense boundary.
+pub(crate) type Pending = Rc<Cell<usize>>;
+
+thread_local! {
+    /// The suspense boundaries above the component being walked, the nearest
+    /// last.
+    static BOUNDARIES: RefCell<Vec<Pending>> = const { RefCell::new(vec![]) };
+}
+
+/// Walks the components below a suspense boundary, which catches the loads
+/// started within.
+pub(crate) fn within<R>(pending: &Pending, walk: impl FnOnce() -> R) -> R {
+    BOUNDARIES.with(|boundaries| boundaries.borrow_mut().push(pending.clone()));
+    let walked = walk();
+    BOUNDARIES.with(|boundaries| boundaries.borrow_mut().pop());
+    walked
+}
+
+/// Starts the `loading` of a component, on the nearest suspense boundary if
+/// any. The returned flag is set once it is loaded.
+pub(crate) fn suspend(loading: Loading, rx_sender: MessageSender) -> Rc<Cell<bool>> {
+    let loaded = Rc::new(Cell::new(false));
+    let pending = BOUNDARIES.with(|boundaries| boundaries.borrow().last().cloned());
+    if let Some(ref pending) = pending {
+        pending.set(pending.get() + 1);
+    }
+
+    let is_loaded = loaded.clone();
+    wasm_bindgen_futures::spawn_local(async move {
+        loading.await;
+        is_loaded.set(true);
+        if let Some(pending) = pending {
+            pending.set(pending.get() - 1);
+        }
+        rx_sender.do_react();
+    });
+    loaded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_track_the_nearest_boundary() {
+        let outer = Rc::new(Cell::new(0));
+        let inner = Rc::new(Cell::new(0));
+        within(&outer, || {
+            within(&inner, || {
+                let nearest = BOUNDARIES.with(|b| b.borrow().last().cloned()).unwrap();
+                assert!(Rc::ptr_eq(&nearest, &inner));
+            });
+            let nearest = BOUNDARIES.with(|b| b.borrow().last().cloned()).unwrap();
+            assert!(Rc::ptr_eq(&nearest, &outer));
+        });
+        assert!(BOUNDARIES.with(|b| b.borrow().is_empty()));
+    }
+}
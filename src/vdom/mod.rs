@@ -7,6 +7,7 @@ use crate::{
         vcomponent::VComponent,
         velement::VElement,
         vlist::VList,
+        vraw::VRaw,
         vtext::VText
     },
     MessageSender,
@@ -19,9 +20,12 @@ use std::{
 use wasm_bindgen::prelude::JsValue;
 use web_sys::Node;
 
+pub mod safety;
+pub mod transition;
 pub mod vcomponent;
 pub mod velement;
 pub mod vlist;
+pub mod vraw;
 pub mod vtext;
 mod conversions;
 
@@ -35,6 +39,8 @@ pub enum VNode<RCTX: Render> {
     List(VList<RCTX>),
     /// A component vnode
     Component(VComponent<RCTX>),
+    /// A trusted raw html vnode
+    Raw(VRaw<RCTX>),
     /// The empty variant
     None
 }
@@ -47,6 +53,19 @@ impl<RCTX: Render> VNode<RCTX> {
             _ => false
         }
     }
+
+    /// Create a VNode from trusted raw HTML. The markup is rendered inside a
+    /// `div` container without being escaped.
+    ///
+    /// # Example
+    /// ```
+    /// # use ruukh::vdom::VNode;
+    /// let highlighted = VNode::<()>::dangerously_raw("<b>fn</b> main() {}");
+    /// assert_eq!(format!("{}", highlighted), "<div><b>fn</b> main() {}</div>");
+    /// ```
+    pub fn dangerously_raw(content: impl Into<String>) -> VNode<RCTX> {
+        VNode::from(VRaw::dangerously_new(content))
+    }
 }
 
 impl<RCTX: Render> Display for VNode<RCTX> {
@@ -56,6 +75,7 @@ impl<RCTX: Render> Display for VNode<RCTX> {
             VNode::Element(inner) => write!(f, "{}", inner),
             VNode::List(inner) => write!(f, "{}", inner),
             VNode::Component(inner) => write!(f, "{}", inner),
+            VNode::Raw(inner) => write!(f, "{}", inner),
             VNode::None => Ok(())
         }
     }
@@ -102,6 +122,7 @@ impl<RCTX: Render> DOMPatch for VNode<RCTX> {
             VNode::Component(ref mut comp) => comp.render_walk(parent, next, render_ctx, rx_sender),
             // There is nothing to walk on.
             VNode::Text(_) => Ok(()),
+            VNode::Raw(_) => Ok(()),
             VNode::None => Ok(())
         }
     }
@@ -127,6 +148,9 @@ impl<RCTX: Render> DOMPatch for VNode<RCTX> {
             VNode::Component(ref mut new_comp) => {
                 patch!(Component => new_comp, old, parent, next, render_ctx, rx_sender)
             }
+            VNode::Raw(ref mut new_raw) => {
+                patch!(Raw => new_raw, old, parent, next, render_ctx, rx_sender)
+            }
             VNode::None => {
                 if let Some(old) = old {
                     old.remove(parent)?;
@@ -142,6 +166,7 @@ impl<RCTX: Render> DOMPatch for VNode<RCTX> {
             VNode::Element(el) => el.reorder(parent, next),
             VNode::List(li) => li.reorder(parent, next),
             VNode::Component(comp) => comp.reorder(parent, next),
+            VNode::Raw(raw) => raw.reorder(parent, next),
             VNode::None => Ok(())
         }
     }
@@ -152,6 +177,7 @@ impl<RCTX: Render> DOMPatch for VNode<RCTX> {
             VNode::Element(el) => el.remove(parent),
             VNode::List(li) => li.remove(parent),
             VNode::Component(comp) => comp.remove(parent),
+            VNode::Raw(raw) => raw.remove(parent),
             VNode::None => Ok(())
         }
     }
@@ -162,6 +188,7 @@ impl<RCTX: Render> DOMPatch for VNode<RCTX> {
             VNode::Element(el) => el.node(),
             VNode::List(li) => li.node(),
             VNode::Component(comp) => comp.node(),
+            VNode::Raw(raw) => raw.node(),
             VNode::None => None
         }
     }
@@ -174,7 +201,7 @@ impl<RCTX: Render> DOMPatch for VNode<RCTX> {
 /// 
 /// Note:
 /// WASM only supported 32-bit and 64-bit of the integers.
-#[derive(Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum Key {
     /// An `i32` key
     I32(i32),
@@ -0,0 +1,196 @@
+//! Escaping of the rendered markup and the policy for the URLs passed in the
+//! attributes.
+//!
+//! Text content and attribute values are always escaped when the VDOM is
+//! rendered as a string. The URLs in `href`/`src` attributes are checked
+//! against the current [UrlPolicy](enum.UrlPolicy.html), both when rendered as
+//! a string and when patched onto the DOM.
+//!
+//! # Example
+//! ```
+//! use ruukh::vdom::safety::{set_url_policy, UrlPolicy};
+//!
+//! set_url_policy(UrlPolicy::Neutralize);
+//! ```
+
+use std::{
+    cell::Cell,
+    fmt::{self, Display, Formatter},
+};
+
+/// The URL which replaces a neutralized URL.
+pub const NEUTRALIZED_URL: &str = "about:blank#blocked";
+
+/// The attributes whose values are URLs that may be navigated to.
+const URL_ATTRIBUTES: [&str; 2] = ["href", "src"];
+
+/// Policy to apply on URLs in the `href`/`src` attributes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UrlPolicy {
+    /// Allow any URL as is. This is the default.
+    #[default]
+    Allow,
+    /// Replace `javascript:` URLs with
+    /// [NEUTRALIZED_URL](constant.NEUTRALIZED_URL.html).
+    Neutralize,
+}
+
+thread_local! {
+    static URL_POLICY: Cell<UrlPolicy> = Cell::new(UrlPolicy::default());
+}
+
+/// Sets the URL policy for all the markup rendered afterwards on this thread.
+pub fn set_url_policy(policy: UrlPolicy) {
+    URL_POLICY.with(|current| current.set(policy));
+}
+
+/// Gets the URL policy in effect.
+pub fn url_policy() -> UrlPolicy {
+    URL_POLICY.with(|current| current.get())
+}
+
+/// Applies the current URL policy on the value of the attribute `key`.
+pub(crate) fn apply_url_policy<'a>(key: &str, value: &'a str) -> &'a str {
+    if url_policy() == UrlPolicy::Neutralize
+        && URL_ATTRIBUTES
+            .iter()
+            .any(|attribute| attribute.eq_ignore_ascii_case(key))
+        && is_javascript_url(value)
+    {
+        NEUTRALIZED_URL
+    } else {
+        value
+    }
+}
+
+/// Whether the `name` may be used as the name of an attribute. It may not be
+/// empty, nor contain whitespace, control characters or any of `"'>/=`, which
+/// would break out of the attribute in the markup.
+pub fn is_valid_attribute_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control() || "\"'>/=".contains(c))
+}
+
+/// Whether the URL uses the `javascript:` scheme.
+///
+/// Browsers ignore leading whitespace & control characters as well as
+/// embedded tabs & newlines in the scheme, so these are skipped too.
+fn is_javascript_url(url: &str) -> bool {
+    const SCHEME: &str = "javascript:";
+
+    let mut scheme = url
+        .trim_start_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !['\t', '\n', '\r'].contains(c));
+    SCHEME
+        .chars()
+        .all(|expected| scheme.next().map(|c| c.to_ascii_lowercase()) == Some(expected))
+}
+
+/// Displays the text content escaped for use in the markup.
+pub(crate) struct EscapedText<'a>(pub &'a str);
+
+impl<'a> Display for EscapedText<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        escape(f, self.0, |c| match c {
+            '&' => Some("&amp;"),
+            '<' => Some("&lt;"),
+            '>' => Some("&gt;"),
+            _ => None,
+        })
+    }
+}
+
+/// Displays the attribute value escaped for use within double quotes.
+pub(crate) struct EscapedAttribute<'a>(pub &'a str);
+
+impl<'a> Display for EscapedAttribute<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        escape(f, self.0, |c| match c {
+            '&' => Some("&amp;"),
+            '"' => Some("&quot;"),
+            '<' => Some("&lt;"),
+            '>' => Some("&gt;"),
+            _ => None,
+        })
+    }
+}
+
+/// Writes the `content` while replacing the characters with the entities
+/// given by `entity_of`.
+fn escape(
+    f: &mut Formatter<'_>,
+    content: &str,
+    entity_of: impl Fn(char) -> Option<&'static str>,
+) -> fmt::Result {
+    let mut last = 0;
+    for (index, c) in content.char_indices() {
+        if let Some(entity) = entity_of(c) {
+            f.write_str(&content[last..index])?;
+            f.write_str(entity)?;
+            last = index + c.len_utf8();
+        }
+    }
+    f.write_str(&content[last..])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_escape_text() {
+        assert_eq!(
+            EscapedText("<script>alert('&')</script>").to_string(),
+            "&lt;script&gt;alert('&amp;')&lt;/script&gt;"
+        );
+    }
+
+    #[test]
+    fn should_escape_attribute() {
+        assert_eq!(
+            EscapedAttribute(r#"" onclick="alert(1)"#).to_string(),
+            "&quot; onclick=&quot;alert(1)"
+        );
+    }
+
+    #[test]
+    fn should_detect_javascript_urls() {
+        assert!(is_javascript_url("javascript:alert(1)"));
+        assert!(is_javascript_url("  JavaScript:alert(1)"));
+        assert!(is_javascript_url("java\tscript:alert(1)"));
+        assert!(!is_javascript_url("https://www.rust-lang.org/"));
+        assert!(!is_javascript_url("/javascript:"));
+    }
+
+    #[test]
+    fn should_neutralize_javascript_urls_only_when_asked() {
+        set_url_policy(UrlPolicy::Allow);
+        assert_eq!(apply_url_policy("href", "javascript:void(0)"), "javascript:void(0)");
+
+        set_url_policy(UrlPolicy::Neutralize);
+        assert_eq!(apply_url_policy("href", "javascript:void(0)"), NEUTRALIZED_URL);
+        assert_eq!(apply_url_policy("src", "javascript:void(0)"), NEUTRALIZED_URL);
+        assert_eq!(apply_url_policy("title", "javascript:void(0)"), "javascript:void(0)");
+        assert_eq!(apply_url_policy("href", "/home"), "/home");
+        assert_eq!(apply_url_policy("HREF", "javascript:void(0)"), NEUTRALIZED_URL);
+        set_url_policy(UrlPolicy::Allow);
+    }
+
+    #[test]
+    fn should_validate_attribute_names() {
+        assert!(is_valid_attribute_name("aria-label"));
+        assert!(is_valid_attribute_name("data-id"));
+        assert!(is_valid_attribute_name("xlink:href"));
+        assert!(!is_valid_attribute_name(""));
+        assert!(!is_valid_attribute_name("data id"));
+        assert!(!is_valid_attribute_name("a\"b"));
+        assert!(!is_valid_attribute_name("a'b"));
+        assert!(!is_valid_attribute_name("a>b"));
+        assert!(!is_valid_attribute_name("a/b"));
+        assert!(!is_valid_attribute_name("onclick=alert(1)"));
+        assert!(!is_valid_attribute_name("a\u{0}b"));
+    }
+}
@@ -0,0 +1,257 @@
+//! Enter & leave transitions of the elements inserted into & removed from the
+//! DOM.
+
+use crate::{component::Render, dom, vdom::VNode};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use wasm_bindgen::{prelude::*, JsCast};
+use web_sys::{window, Element, Event, Node};
+
+/// The time in milliseconds after which a transition is taken to be done, if
+/// it did not end by then.
+pub const DEFAULT_TIMEOUT: i32 = 1000;
+
+/// Transitions the elements in & out of the DOM by applying CSS classes in
+/// phases, named after the transition.
+///
+/// When an element is inserted, `{name}-enter` & `{name}-enter-active` are
+/// applied. On the next frame `{name}-enter` is replaced with
+/// `{name}-enter-to`, and both the others are removed once the transition or
+/// animation ends. Likewise, when removed, `{name}-leave`, `{name}-leave-active`
+/// & `{name}-leave-to` are applied & the element is removed only once it
+/// ends. The leaving element stays in its place, though it no longer reacts
+/// to events.
+///
+/// # Example
+/// ```ignore
+/// html! {
+///     { Transition::new("fade").wrap(html! {
+///         if self.saved {
+///             <p>"Saved"</p>
+///         }
+///     }) }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transition {
+    name: Cow<'static, str>,
+    timeout: i32,
+}
+
+impl Transition {
+    /// Create a transition with the `name` prefixed to its classes.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Transition {
+        Transition {
+            name: name.into(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sets the time in milliseconds after which the transition is taken to
+    /// be done, if it did not end by then.
+    pub fn timeout(mut self, timeout: i32) -> Transition {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Applies the transition to the element of the `vnode`, or to each of the
+    /// elements if it is a list.
+    ///
+    /// Texts, raw HTML & components are left as they are, as they have no
+    /// element of their own to transition. Wrap the markup within a component
+    /// to transition it instead.
+    pub fn wrap<RCTX: Render>(&self, mut vnode: VNode<RCTX>) -> VNode<RCTX> {
+        self.apply(&mut vnode);
+        vnode
+    }
+
+    fn apply<RCTX: Render>(&self, vnode: &mut VNode<RCTX>) {
+        match vnode {
+            VNode::Element(el) => el.set_transition(self.clone()),
+            VNode::List(list) => {
+                for (_, vnode) in list.iter_mut() {
+                    self.apply(vnode);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn class(&self, phase: &str) -> String {
+        format!("{}-{}", self.name, phase)
+    }
+
+    /// Prepares the element to enter, before it is inserted.
+    pub(crate) fn before_enter(&self, el: &Element) -> Result<(), JsValue> {
+        let class_list = el.class_list();
+        class_list.add_1(&self.class("enter"))?;
+        class_list.add_1(&self.class("enter-active"))?;
+        Ok(())
+    }
+
+    /// Transitions the element in, once it is inserted.
+    pub(crate) fn enter(&self, el: &Element) -> Result<(), JsValue> {
+        let transition = self.clone();
+        let el = el.clone();
+        next_frame(move || {
+            let class_list = el.class_list();
+            class_list.remove_1(&transition.class("enter"))?;
+            class_list.add_1(&transition.class("enter-to"))?;
+            let timeout = transition.timeout;
+            after_end(&el.clone(), timeout, move || {
+                let class_list = el.class_list();
+                let _ = class_list.remove_2(
+                    &transition.class("enter-active"),
+                    &transition.class("enter-to"),
+                );
+            })
+        })
+    }
+
+    /// Transitions the element out in place of the `el`, which is removed
+    /// once it ends.
+    pub(crate) fn leave(&self, el: &Element, parent: &Node) -> Result<(), JsValue> {
+        let leaving: Element = el.clone_node_with_deep(true)?.unchecked_into();
+        let class_list = leaving.class_list();
+        class_list.add_1(&self.class("leave"))?;
+        class_list.add_1(&self.class("leave-active"))?;
+        dom::count_op();
+        parent.replace_child(leaving.as_ref(), el.as_ref())?;
+
+        let transition = self.clone();
+        next_frame(move || {
+            let class_list = leaving.class_list();
+            class_list.remove_1(&transition.class("leave"))?;
+            class_list.add_1(&transition.class("leave-to"))?;
+            after_end(&leaving.clone(), transition.timeout, move || {
+                if let Some(parent) = leaving.parent_node() {
+                    let _ = parent.remove_child(leaving.as_ref());
+                }
+            })
+        })
+    }
+}
+
+thread_local! {
+    /// Whether an element is being removed, so that the ones within it are
+    /// removed along with it instead of leaving on their own.
+    static REMOVING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether the element is removed along with an outer element.
+pub(crate) fn is_removing() -> bool {
+    REMOVING.with(Cell::get)
+}
+
+/// Removes the children of an element with `remove`, which leave along with
+/// it.
+pub(crate) fn removing<R>(remove: impl FnOnce() -> R) -> R {
+    let was_removing = REMOVING.with(|removing| removing.replace(true));
+    let removed = remove();
+    REMOVING.with(|removing| removing.set(was_removing));
+    removed
+}
+
+/// Runs `f` on the next animation frame.
+fn next_frame(f: impl FnOnce() -> Result<(), JsValue> + 'static) -> Result<(), JsValue> {
+    let callback = Closure::once_into_js(move || {
+        let _ = f();
+    });
+    window()
+        .unwrap()
+        .request_animation_frame(callback.unchecked_ref())?;
+    Ok(())
+}
+
+/// Runs `done` once the transition or animation of the element ends, or else
+/// after the `timeout`.
+fn after_end(el: &Element, timeout: i32, done: impl FnOnce() + 'static) -> Result<(), JsValue> {
+    let done = Rc::new(RefCell::new(Some(done)));
+    let finish = move || {
+        let done = done.borrow_mut().take();
+        if let Some(done) = done {
+            done();
+        }
+    };
+    let finish = Rc::new(finish);
+
+    let target = el.clone();
+    let on_end = {
+        let finish = finish.clone();
+        Closure::wrap(Box::new(move |event: Event| {
+            // The transitions of the children bubble up too.
+            let target: &JsValue = target.as_ref();
+            if event.target().map(JsValue::from).as_ref() == Some(target) {
+                finish();
+            }
+        }) as Box<dyn Fn(Event)>)
+    };
+    el.add_event_listener_with_callback("transitionend", on_end.as_ref().unchecked_ref())?;
+    el.add_event_listener_with_callback("animationend", on_end.as_ref().unchecked_ref())?;
+
+    // The timeout runs either way, so the listener is dropped along with it.
+    let el = el.clone();
+    let on_timeout = Closure::once_into_js(move || {
+        let listener = on_end.as_ref().unchecked_ref();
+        let _ = el.remove_event_listener_with_callback("transitionend", listener);
+        let _ = el.remove_event_listener_with_callback("animationend", listener);
+        finish();
+    });
+    window()
+        .unwrap()
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            on_timeout.unchecked_ref(),
+            timeout,
+        )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[test]
+    fn should_name_classes_after_transition() {
+        let transition = Transition::new("fade").timeout(300);
+        assert_eq!(transition.class("enter-active"), "fade-enter-active");
+        assert_eq!(transition.timeout, 300);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_apply_enter_classes() {
+        let el = window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .create_element("p")
+            .unwrap();
+        Transition::new("fade").before_enter(&el).unwrap();
+        assert_eq!(el.class_name(), "fade-enter fade-enter-active");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_keep_leaving_element_in_place() {
+        let parent = window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .create_element("div")
+            .unwrap();
+        parent.set_inner_html("<p>First</p><p>Second</p>");
+        let first: Element = parent.first_element_child().unwrap();
+
+        Transition::new("fade")
+            .leave(&first, parent.as_ref())
+            .unwrap();
+        assert_eq!(
+            parent.inner_html(),
+            r#"<p class="fade-leave fade-leave-active">First</p><p>Second</p>"#
+        );
+        assert!(first.parent_node().is_none());
+    }
+
+}
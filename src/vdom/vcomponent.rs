@@ -3,17 +3,24 @@
 use crate::{
     component::{FromEventProps, Render, Status},
     dom::DOMPatch,
-    vdom::{Shared, VNode},
+    error::{self, CaughtError},
+    hooks::{self, Hooks},
+    suspense::{self, Pending},
+    vdom::{vtext::VText, Shared, VNode},
     MessageSender,
 };
+#[cfg(feature = "devtools")]
+use crate::{devtools, vdom::Key};
+#[cfg(feature = "profiler")]
+use crate::profiler::{Cause, Profile};
 use std::{
-    any::Any,
-    cell::RefCell,
+    any::{type_name, Any},
+    cell::{Cell, RefCell},
     fmt::{self, Display, Formatter},
     rc::Rc,
 };
 use wasm_bindgen::prelude::JsValue;
-use web_sys::Node;
+use web_sys::{window, Node};
 
 /// The representation of a component in a Virtual DOM.
 pub struct VComponent<RCTX: Render>(Box<dyn ComponentManager<RenderContext = RCTX>>);
@@ -29,6 +36,11 @@ impl<RCTX: Render> VComponent<RCTX> {
     {
         VComponent(Box::new(ComponentWrapper::<COMP, RCTX>::new(props, events)))
     }
+    /// The manager of the component.
+    #[cfg(feature = "devtools")]
+    pub(crate) fn manager(&self) -> &dyn ComponentManager<RenderContext = RCTX> {
+        &*self.0
+    }
 }
 
 pub(crate) struct ComponentWrapper<COMP: Render, RCTX: Render>
@@ -39,6 +51,26 @@ where
     props: Option<COMP::Props>,
     events: Option<<COMP::Events as FromEventProps<RCTX>>::From>,
     cached_render: Option<VNode<COMP>>,
+    hooks: Option<Hooks>,
+    /// Set once the component is loaded, while it is suspended.
+    loading: Option<Rc<Cell<bool>>>,
+    boundary: Option<Boundary<COMP>>,
+    /// Set once it shows its error fallback, till its props change.
+    failed: bool,
+}
+
+/// A suspense boundary, which shows its fallback while any of the components
+/// within it are loading.
+struct Boundary<COMP: Render> {
+    pending: Pending,
+    suspended: Option<Suspended<COMP>>,
+}
+
+/// The fallback shown while the render is kept aside in an offscreen
+/// container.
+struct Suspended<COMP: Render> {
+    fallback: VNode<COMP>,
+    offscreen: Node,
 }
 
 impl<COMP: Render, RCTX: Render> ComponentWrapper<COMP, RCTX>
@@ -54,7 +86,315 @@ where
             props: Some(props),
             events: Some(events),
             cached_render: None,
+            hooks: None,
+            loading: None,
+            boundary: None,
+            failed: false,
+        }
+    }
+
+    /// Renders the component if it is new or changed & walks the components
+    /// within it.
+    fn walk(
+        &mut self,
+        parent: &Node,
+        next: Option<&Node>,
+        render_ctx: Shared<RCTX>,
+        rx_sender: MessageSender,
+    ) -> Result<(), JsValue> {
+        // A suspended component is rendered only once it is loaded.
+        let mut is_just_loaded = false;
+        match self.loading.as_ref().map(|loaded| loaded.get()) {
+            Some(false) => return Ok(()),
+            Some(true) => {
+                self.loading = None;
+                is_just_loaded = true;
+            }
+            None => {}
+        }
+
+        // The render is kept aside while the boundary shows its fallback.
+        let offscreen = self
+            .suspended()
+            .map(|suspended| suspended.offscreen.clone());
+        let (content_parent, content_next) = match offscreen {
+            Some(ref offscreen) => (offscreen, None),
+            None => (parent, next),
+        };
+
+        // Whether the component is rendered & if it is the first render.
+        let mut rendered = None;
+        #[cfg(feature = "profiler")]
+        let mut cause = Cause::Mount;
+        if self.component.is_none() {
+            let props = self.props.take().unwrap();
+            let events = self.events.take().unwrap();
+            let state = COMP::initial_state(&props);
+            let instance = COMP::init(
+                props,
+                FromEventProps::from(events, render_ctx),
+                Status::new(state, rx_sender.clone()),
+            );
+            instance.created();
+            if instance.fallback().is_some() {
+                self.boundary = Some(Boundary {
+                    pending: Rc::new(Cell::new(0)),
+                    suspended: None,
+                });
+            }
+
+            let loading = instance.load();
+            let shared_instance = Rc::new(RefCell::new(instance));
+            self.component = Some(shared_instance.clone());
+            if let Some(loading) = loading {
+                // Keep the place of the component till it is loaded.
+                let mut placeholder = VNode::Text(VText::comment(""));
+                placeholder.patch(None, parent, next, shared_instance, rx_sender.clone())?;
+                self.cached_render = Some(placeholder);
+                self.loading = Some(suspense::suspend(loading, rx_sender));
+                return Ok(());
+            }
+            rendered = Some(true);
+        } else {
+            let comp = self.component.as_ref().unwrap();
+
+            let state_changed = comp
+                .borrow()
+                .status()
+                .map(|s| s.borrow().is_state_dirty())
+                .unwrap_or(false);
+            let state_changed = if state_changed {
+                comp.borrow()
+                    .status()
+                    .unwrap()
+                    .borrow_mut()
+                    .set_state_dirty(false);
+                comp.borrow_mut().refresh_state()
+            } else {
+                false
+            };
+
+            let hooks_changed = self.hooks.as_ref().is_some_and(Hooks::take_dirty);
+            let state_changed = state_changed || hooks_changed;
+
+            let props_changed = comp
+                .borrow()
+                .status()
+                .map(|s| s.borrow().is_props_dirty())
+                .unwrap_or(false);
+            if props_changed {
+                comp.borrow()
+                    .status()
+                    .unwrap()
+                    .borrow_mut()
+                    .set_props_dirty(false);
+            }
+
+            let forced = comp
+                .borrow()
+                .status()
+                .map(|s| s.borrow().is_forced())
+                .unwrap_or(false);
+            if forced {
+                comp.borrow().status().unwrap().borrow_mut().set_forced(false);
+            }
+
+            // A failed error boundary keeps showing its fallback till its
+            // props change, so that it does not fail over & over again.
+            if props_changed {
+                self.failed = false;
+            }
+
+            // A change in props is already vetted by `should_render` on patch.
+            let should_render = !self.failed
+                && (is_just_loaded
+                    || forced
+                    || props_changed
+                    || (state_changed && comp.borrow().should_render(None)));
+
+            if should_render {
+                if !is_just_loaded {
+                    comp.borrow().before_update();
+                }
+                #[cfg(feature = "profiler")]
+                {
+                    if !is_just_loaded {
+                        cause = Cause::new(forced, props_changed, state_changed);
+                    }
+                }
+                rendered = Some(is_just_loaded);
+            }
+        }
+        let comp = self.component.as_ref().unwrap().clone();
+
+        let patched = match rendered {
+            Some(first_render) => {
+                #[cfg(feature = "profiler")]
+                let mut profile = Profile::start(type_name::<COMP>(), cause);
+                let hooks = &mut self.hooks;
+                let render = error::catch_panic(|| {
+                    hooks::render(hooks, &rx_sender, first_render, || comp.borrow().render())
+                }).map_err(|message| JsValue::from_str(&message));
+                #[cfg(feature = "profiler")]
+                profile.rendered();
+                let patched = render.and_then(|render| {
+                    self.patch_render(render, content_parent, content_next, rx_sender.clone())
+                });
+                #[cfg(feature = "profiler")]
+                profile.finish();
+                patched
+            }
+            None => Ok(()),
+        };
+        let is_patched = patched.is_ok();
+        let walked = patched.and_then(|()| {
+            let cached = match self.cached_render {
+                Some(ref mut cached) => cached,
+                None => return Ok(()),
+            };
+            let mut walk = || {
+                error::catch_panic(|| {
+                    cached.render_walk(
+                        content_parent,
+                        content_next,
+                        comp.clone(),
+                        rx_sender.clone(),
+                    )
+                }).unwrap_or_else(|message| Err(JsValue::from_str(&message)))
+            };
+            match self.boundary {
+                Some(ref boundary) => suspense::within(&boundary.pending, walk),
+                None => walk(),
+            }
+        });
+        if let Err(error) = walked {
+            self.catch_error(error, content_parent, content_next, rx_sender.clone())?;
+        }
+        self.settle_boundary(parent, next, rx_sender)?;
+        if let Some(first_render) = rendered {
+            if first_render {
+                comp.borrow().mounted();
+            }
+            comp.borrow().rendered(first_render);
+            // Effects run once the whole subtree is patched, unless the render
+            // itself failed.
+            if is_patched {
+                if let Some(ref mut hooks) = self.hooks {
+                    hooks::run_effects(hooks);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Patches the DOM with the `render` over the cached one. If the patch
+    /// fails, the cached render is removed, while the new one is cached in its
+    /// place, so that whatever of it got into the DOM is removed along with it.
+    fn patch_render(
+        &mut self,
+        mut render: VNode<COMP>,
+        parent: &Node,
+        next: Option<&Node>,
+        rx_sender: MessageSender,
+    ) -> Result<(), JsValue> {
+        let comp = self.component.as_ref().unwrap().clone();
+        let mut cached_render = self.cached_render.take();
+        let patched = render.patch(cached_render.as_mut(), parent, next, comp, rx_sender);
+        if patched.is_err() {
+            if let Some(failed) = cached_render {
+                failed.remove(parent)?;
+            }
+        }
+        self.cached_render = Some(render);
+        patched
+    }
+
+    /// Shows the error fallback in place of the render if it is an error
+    /// boundary, or else passes the error on.
+    fn catch_error(
+        &mut self,
+        error: JsValue,
+        parent: &Node,
+        next: Option<&Node>,
+        rx_sender: MessageSender,
+    ) -> Result<(), JsValue> {
+        let comp = self.component.as_ref().unwrap().clone();
+        let caught = CaughtError {
+            error,
+            path: error::path(),
+        };
+        let fallback = comp.borrow().error_fallback(&caught);
+        let mut fallback = match fallback {
+            Some(fallback) => fallback,
+            None => return Err(caught.error),
+        };
+        error::clear_path();
+
+        // Remove the failed render & show the fallback in its place.
+        if let Some(failed) = self.cached_render.take() {
+            failed.remove(parent)?;
+        }
+        fallback.patch(None, parent, next, comp.clone(), rx_sender.clone())?;
+        fallback.render_walk(parent, next, comp.clone(), rx_sender)?;
+        self.cached_render = Some(fallback);
+        self.failed = true;
+        comp.borrow().caught_error(caught);
+        Ok(())
+    }
+
+    /// The fallback shown, if it is a suspended boundary.
+    fn suspended(&self) -> Option<&Suspended<COMP>> {
+        self.boundary
+            .as_ref()
+            .and_then(|boundary| boundary.suspended.as_ref())
+    }
+
+    /// Shows the fallback of the boundary while there are pending loads within
+    /// it, and patches the render back in once they are done.
+    fn settle_boundary(
+        &mut self,
+        parent: &Node,
+        next: Option<&Node>,
+        rx_sender: MessageSender,
+    ) -> Result<(), JsValue> {
+        let boundary = match self.boundary {
+            Some(ref mut boundary) => boundary,
+            None => return Ok(()),
+        };
+        let cached_render = self.cached_render.as_ref().unwrap();
+        let comp = self.component.as_ref().unwrap();
+        let is_pending = boundary.pending.get() > 0;
+
+        match boundary.suspended.take() {
+            None if is_pending => {
+                // Keep the render aside & show the fallback in its place.
+                let offscreen: Node = window()
+                    .unwrap()
+                    .document()
+                    .unwrap()
+                    .create_element("div")?
+                    .into();
+                cached_render.reorder(&offscreen, None)?;
+                let mut fallback = comp.borrow().fallback().unwrap_or(VNode::None);
+                fallback.patch(None, parent, next, comp.clone(), rx_sender.clone())?;
+                fallback.render_walk(parent, next, comp.clone(), rx_sender)?;
+                boundary.suspended = Some(Suspended {
+                    fallback,
+                    offscreen,
+                });
+            }
+            Some(suspended) => {
+                if is_pending {
+                    boundary.suspended = Some(suspended);
+                } else {
+                    // Patch the render back in over the fallback.
+                    cached_render.reorder(parent, suspended.fallback.node().or(next))?;
+                    suspended.fallback.remove(parent)?;
+                }
+            }
+            None => {}
         }
+        Ok(())
     }
 }
 
@@ -123,6 +463,15 @@ pub(crate) trait ComponentManager: Display + 'static {
     fn node(&self) -> Option<&Node>;
 
     fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Inspects the component along with its child components.
+    #[cfg(feature = "devtools")]
+    fn inspect(&self, key: Option<&Key>) -> devtools::Node;
+
+    /// Edits the state of the component found at the `path` of indexes of the
+    /// child components.
+    #[cfg(feature = "devtools")]
+    fn edit_state(&self, path: &[usize], field: &str, value: &str) -> Result<(), String>;
 }
 
 impl<COMP: Render, RCTX: Render> ComponentManager for ComponentWrapper<COMP, RCTX>
@@ -138,81 +487,12 @@ where
         render_ctx: Shared<Self::RenderContext>,
         rx_sender: MessageSender,
     ) -> Result<(), JsValue> {
-        if self.component.is_none() {
-            let props = self.props.take().unwrap();
-            let events = self.events.take().unwrap();
-            let instance = COMP::init(
-                props,
-                FromEventProps::from(events, render_ctx),
-                Status::new(COMP::State::default(), rx_sender.clone()),
-            );
-            instance.created();
-            let mut initial_render = instance.render();
-            let shared_instance = Rc::new(RefCell::new(instance));
-            initial_render.patch(
-                None,
-                parent,
-                next,
-                shared_instance.clone(),
-                rx_sender.clone(),
-            )?;
-            shared_instance.borrow().mounted();
-            self.component = Some(shared_instance);
-            self.cached_render = Some(initial_render);
-        } else {
-            let comp = self.component.as_ref().unwrap();
-
-            let state_changed = comp
-                .borrow()
-                .status()
-                .map(|s| s.borrow().is_state_dirty())
-                .unwrap_or(false);
-            let state_changed = if state_changed {
-                comp.borrow()
-                    .status()
-                    .unwrap()
-                    .borrow_mut()
-                    .set_state_dirty(false);
-                comp.borrow_mut().refresh_state()
-            } else {
-                false
-            };
-
-            let props_changed = comp
-                .borrow()
-                .status()
-                .map(|s| s.borrow().is_props_dirty())
-                .unwrap_or(false);
-            if props_changed {
-                comp.borrow()
-                    .status()
-                    .unwrap()
-                    .borrow_mut()
-                    .set_props_dirty(false);
-            }
-
-            if state_changed || props_changed {
-                let mut rerender = comp.borrow().render();
-                let mut cached_render = self.cached_render.take();
-                rerender.patch(
-                    cached_render.as_mut(),
-                    parent,
-                    next,
-                    comp.clone(),
-                    rx_sender.clone(),
-                )?;
-                self.cached_render = Some(rerender);
-            }
-        }
-        if let Some(ref mut cached) = self.cached_render {
-            cached.render_walk(
-                parent,
-                next,
-                self.component.as_ref().unwrap().clone(),
-                rx_sender,
-            )?;
+        let walked = self.walk(parent, next, render_ctx, rx_sender);
+        if walked.is_err() {
+            // The failed component & the ones the error propagates through.
+            error::trace(type_name::<COMP>());
         }
-        Ok(())
+        walked
     }
 
     fn patch(
@@ -237,12 +517,23 @@ where
                         .borrow_mut()
                         .update(props, FromEventProps::from(events, render_ctx));
                     if let Some(old_props) = old_props {
-                        comp.borrow().updated(old_props);
+                        let comp = comp.borrow();
+                        if !comp.should_render(Some(&old_props)) {
+                            if let Some(status) = comp.status() {
+                                status.borrow_mut().set_props_dirty(false);
+                            }
+                        }
+                        comp.updated(old_props);
                     }
                     self.component = Some(comp);
 
-                    // Reuse the cached render too to do patches on.
+                    // Reuse the cached render, the hooks & the suspense too to
+                    // do patches on.
                     self.cached_render = old.cached_render.take();
+                    self.hooks = old.hooks.take();
+                    self.loading = old.loading.take();
+                    self.boundary = old.boundary.take();
+                    self.failed = old.failed;
 
                     true
                 }
@@ -257,7 +548,9 @@ where
     }
 
     fn reorder(&self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
-        if let Some(ref cached_render) = self.cached_render {
+        if let Some(suspended) = self.suspended() {
+            suspended.fallback.reorder(parent, next)?;
+        } else if let Some(ref cached_render) = self.cached_render {
             cached_render.reorder(parent, next)?;
         }
         Ok(())
@@ -265,20 +558,82 @@ where
 
     fn remove(&self, parent: &Node) -> Result<(), JsValue> {
         if let Some(ref cached_render) = self.cached_render {
-            cached_render.remove(parent)?;
             let comp = self.component.as_ref().unwrap();
+            comp.borrow().before_destroy();
+            if let Some(suspended) = self.suspended() {
+                suspended.fallback.remove(parent)?;
+                cached_render.remove(&suspended.offscreen)?;
+            } else {
+                cached_render.remove(parent)?;
+            }
+            if let Some(ref hooks) = self.hooks {
+                hooks::clean_up(hooks);
+            }
             comp.borrow().destroyed();
         }
         Ok(())
     }
 
     fn node(&self) -> Option<&Node> {
+        if let Some(suspended) = self.suspended() {
+            return suspended.fallback.node();
+        }
         self.cached_render.as_ref().and_then(|inner| inner.node())
     }
 
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+    #[cfg(feature = "devtools")]
+    fn inspect(&self, key: Option<&Key>) -> devtools::Node {
+        let children = self
+            .cached_render
+            .as_ref()
+            .map(|render| {
+                devtools::child_components(render)
+                    .into_iter()
+                    .map(|(key, child)| child.inspect(key))
+                    .collect()
+            }).unwrap_or_default();
+
+        devtools::Node {
+            name: type_name::<COMP>(),
+            key: key.cloned(),
+            inspection: self
+                .component
+                .as_ref()
+                .and_then(|comp| comp.try_borrow().ok())
+                .and_then(|comp| comp.inspect()),
+            children,
+        }
+    }
+
+    #[cfg(feature = "devtools")]
+    fn edit_state(&self, path: &[usize], field: &str, value: &str) -> Result<(), String> {
+        match path.split_first() {
+            None => {
+                let comp = self
+                    .component
+                    .as_ref()
+                    .ok_or_else(|| "The component is not rendered yet.".to_string())?;
+                let comp = comp
+                    .try_borrow()
+                    .map_err(|_| "The component is being updated.".to_string())?;
+                comp.edit_state(field, value)
+            }
+            Some((&index, rest)) => {
+                let children = self
+                    .cached_render
+                    .as_ref()
+                    .map(devtools::child_components)
+                    .unwrap_or_default();
+                let (_, child) = children
+                    .get(index)
+                    .ok_or_else(|| format!("There is no child component at {}.", index))?;
+                child.edit_state(rest, field, value)
+            }
+        }
+    }
 }
 
 impl<RCTX: Render> From<VComponent<RCTX>> for VNode<RCTX> {
@@ -314,7 +669,7 @@ pub mod test {
     use crate::{
         component::*,
         prelude::*,
-        vdom::{test::container, velement::*, vtext::*, VNode},
+        vdom::{test::container, velement::*, VNode},
         Shared,
     };
     use wasm_bindgen_test::*;
@@ -431,4 +786,219 @@ pub mod test {
             r#"<button disabled="true">Click</button>"#
         );
     }
+
+    struct Logged {
+        logged_at: u32,
+        __status: Shared<Status<()>>,
+    }
+
+    struct LoggedProps {
+        logged_at: u32,
+    }
+
+    impl Lifecycle for Logged {
+        fn should_render(&self, _: Option<&LoggedProps>) -> bool {
+            false
+        }
+    }
+
+    impl Component for Logged {
+        type Props = LoggedProps;
+        type Events = ();
+        type State = ();
+
+        fn init(props: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+            Logged {
+                logged_at: props.logged_at,
+                __status: Rc::new(RefCell::new(status)),
+            }
+        }
+
+        fn update(&mut self, props: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            if self.logged_at != props.logged_at {
+                let logged_at = self.logged_at;
+                self.logged_at = props.logged_at;
+                self.__status.borrow_mut().set_props_dirty(true);
+                Some(LoggedProps { logged_at })
+            } else {
+                None
+            }
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            unreachable!()
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            Some(&self.__status)
+        }
+    }
+
+    impl Render for Logged {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VText::text(self.logged_at.to_string()))
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn should_skip_rerender_of_component() {
+        let mut vcomp = VComponent::new::<Logged>(LoggedProps { logged_at: 1 }, ());
+        let div = container();
+        vcomp
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            ).expect("To patch div");
+
+        let mut patched = VComponent::new::<Logged>(LoggedProps { logged_at: 2 }, ());
+        patched
+            .patch(
+                Some(&mut vcomp),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            ).unwrap();
+        patched
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            ).expect("To patch div");
+
+        assert_eq!(div.inner_html(), "1");
+    }
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    struct Hooked {
+        value: u32,
+        log: Log,
+        __status: Shared<Status<()>>,
+    }
+
+    struct HookedProps {
+        value: u32,
+        log: Log,
+    }
+
+    impl Lifecycle for Hooked {
+        fn before_update(&self) {
+            self.log.borrow_mut().push(format!("before_update {}", self.value));
+        }
+
+        fn rendered(&self, first_render: bool) {
+            self.log
+                .borrow_mut()
+                .push(format!("rendered {} {}", self.value, first_render));
+        }
+
+        fn before_destroy(&self) {
+            self.log.borrow_mut().push("before_destroy".to_string());
+        }
+
+        fn destroyed(&self) {
+            self.log.borrow_mut().push("destroyed".to_string());
+        }
+    }
+
+    impl Component for Hooked {
+        type Props = HookedProps;
+        type Events = ();
+        type State = ();
+
+        fn init(props: Self::Props, _: Self::Events, status: Status<Self::State>) -> Self {
+            Hooked {
+                value: props.value,
+                log: props.log,
+                __status: Rc::new(RefCell::new(status)),
+            }
+        }
+
+        fn update(&mut self, props: Self::Props, _: Self::Events) -> Option<Self::Props> {
+            if self.value != props.value {
+                let value = self.value;
+                self.value = props.value;
+                self.__status.borrow_mut().set_props_dirty(true);
+                Some(HookedProps {
+                    value,
+                    log: props.log,
+                })
+            } else {
+                None
+            }
+        }
+
+        fn refresh_state(&mut self) -> bool {
+            unreachable!()
+        }
+
+        fn status(&self) -> Option<&Shared<Status<Self::State>>> {
+            Some(&self.__status)
+        }
+    }
+
+    impl Render for Hooked {
+        fn render(&self) -> Markup<Self> {
+            VNode::from(VText::text(self.value.to_string()))
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn should_invoke_lifecycle_hooks_in_order() {
+        let log = Log::default();
+        let mut vcomp = VComponent::new::<Hooked>(
+            HookedProps {
+                value: 1,
+                log: log.clone(),
+            },
+            (),
+        );
+        let div = container();
+        vcomp
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            ).expect("To patch div");
+
+        let mut patched = VComponent::new::<Hooked>(
+            HookedProps {
+                value: 2,
+                log: log.clone(),
+            },
+            (),
+        );
+        patched
+            .patch(
+                Some(&mut vcomp),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            ).unwrap();
+        patched
+            .render_walk(
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            ).expect("To patch div");
+        patched.remove(div.as_ref()).expect("To remove from div");
+
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                "rendered 1 true",
+                "before_update 2",
+                "rendered 2 false",
+                "before_destroy",
+                "destroyed",
+            ]
+        );
+    }
 }
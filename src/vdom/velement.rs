@@ -1,15 +1,26 @@
 //! Element representation in a VDOM.
 
-use crate::{component::Render, dom::DOMPatch, vdom::VNode, MessageSender, Shared};
-use indexmap::IndexMap;
+use crate::{
+    component::Render,
+    dom::{self, DOMPatch},
+    vdom::{
+        safety::{self, apply_url_policy, EscapedAttribute},
+        transition::{self, Transition},
+        VNode,
+    },
+    MessageSender, Shared,
+};
+use indexmap::{IndexMap, IndexSet};
+use js_sys::Reflect;
 use std::{
     borrow::Cow,
     cell::RefCell,
     fmt::{self, Display, Formatter},
+    iter::FromIterator,
     rc::Rc,
 };
 use wasm_bindgen::{prelude::*, JsCast};
-use web_sys::{window, Element, Event, EventTarget, Node};
+use web_sys::{window, CssStyleDeclaration, Element, Event, EventTarget, Node};
 
 /// The representation of an element in virtual DOM.
 pub struct VElement<RCTX: Render> {
@@ -23,25 +34,47 @@ pub struct VElement<RCTX: Render> {
     child: Box<VNode<RCTX>>,
     /// Element reference to the DOM
     node: Option<Element>,
+    /// The transition when inserted & removed
+    transition: Option<Transition>,
 }
 
 /// A list of attributes.
-struct Attributes(IndexMap<&'static str, AttributeValue>);
+struct Attributes(IndexMap<Cow<'static, str>, AttributeValue>);
 
 /// The key, value pair of the attributes on an element.
 pub struct Attribute {
     /// The key of the attribute
-    key: &'static str,
+    key: Cow<'static, str>,
     /// The value pair of the attribute key
     value: AttributeValue,
 }
 
-/// Either a string or a bool
+/// An owned map of attributes whose keys may be decided at runtime. It can be
+/// spread onto an element with `..attrs` in the markup.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AttributeMap(IndexMap<Cow<'static, str>, AttributeValue>);
+
+/// A list of classes, which is diffed class by class when patched. Built with
+/// the `classes!` macro.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClassList(IndexSet<Cow<'static, str>>);
+
+/// A map of style properties, which is diffed property by property when
+/// patched. Built with the `styles!` macro.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyleMap(IndexMap<Cow<'static, str>, Cow<'static, str>>);
+
+/// Either a string, a bool, a list of classes or a map of styles
+#[derive(Clone, Debug, PartialEq)]
 pub enum AttributeValue {
     /// A string attribute value
     String(String),
     /// A boolean attribute value
     Bool(bool),
+    /// A list of classes
+    Classes(ClassList),
+    /// A map of style properties
+    Styles(StyleMap),
 }
 
 struct EventListeners<RCTX: Render>(Vec<Box<dyn EventManager<RenderContext = RCTX>>>);
@@ -76,6 +109,7 @@ impl<RCTX: Render> VElement<RCTX> {
             ),
             child: Box::new(child),
             node: None,
+            transition: None,
         }
     }
 
@@ -100,20 +134,193 @@ impl<RCTX: Render> VElement<RCTX> {
             ),
             child: Box::new(VNode::None),
             node: None,
+            transition: None,
         }
     }
+    /// Sets the transition of the element when inserted & removed.
+    pub(crate) fn set_transition(&mut self, transition: Transition) {
+        self.transition = Some(transition);
+    }
+
+    /// The child node of the element.
+    #[cfg(feature = "devtools")]
+    pub(crate) fn child(&self) -> &VNode<RCTX> {
+        &self.child
+    }
 }
 
 impl Attribute {
     /// Create an Attribute for a VElement.
-    pub fn new(key: &'static str, value: impl Into<AttributeValue>) -> Attribute {
+    pub fn new(key: impl Into<Cow<'static, str>>, value: impl Into<AttributeValue>) -> Attribute {
         Attribute {
-            key,
+            key: key.into(),
             value: value.into(),
         }
     }
 }
 
+impl AttributeMap {
+    /// Create an empty AttributeMap.
+    pub fn new() -> AttributeMap {
+        AttributeMap::default()
+    }
+
+    /// Inserts an attribute, returning the older value of the key if any.
+    ///
+    /// Panics if the key is not a valid attribute name, which may be checked
+    /// beforehand with `safety::is_valid_attribute_name`.
+    pub fn insert(
+        &mut self,
+        key: impl Into<Cow<'static, str>>,
+        value: impl Into<AttributeValue>,
+    ) -> Option<AttributeValue> {
+        self.0.insert(valid_key(key.into()), value.into())
+    }
+
+    /// Removes the attribute with the given key.
+    pub fn remove(&mut self, key: &str) -> Option<AttributeValue> {
+        self.0.shift_remove(key)
+    }
+
+    /// Gets the value of the attribute with the given key.
+    pub fn get(&self, key: &str) -> Option<&AttributeValue> {
+        self.0.get(key)
+    }
+
+    /// The number of attributes in the map.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether there are no attributes in the map.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Creates the attributes to be passed to a VElement.
+    pub fn to_attributes(&self) -> Vec<Attribute> {
+        self.0
+            .iter()
+            .map(|(k, v)| Attribute {
+                key: k.clone(),
+                value: v.clone(),
+            }).collect()
+    }
+}
+
+impl ClassList {
+    /// Create an empty ClassList.
+    pub fn new() -> ClassList {
+        ClassList::default()
+    }
+
+    /// Adds the class. Whitespace separated classes are added individually.
+    pub fn add(&mut self, class: impl Into<Cow<'static, str>>) {
+        match class.into() {
+            Cow::Borrowed(class) => self.0.extend(class.split_whitespace().map(Cow::Borrowed)),
+            Cow::Owned(class) => self.0.extend(
+                class
+                    .split_whitespace()
+                    .map(|class| Cow::Owned(class.to_string())),
+            ),
+        }
+    }
+
+    /// Whether the class is in the list.
+    pub fn contains(&self, class: &str) -> bool {
+        self.0.contains(class)
+    }
+
+    fn patch(&self, old: &ClassList, el: &Element) -> Result<(), JsValue> {
+        let class_list = el.class_list();
+        for class in old.0.iter().filter(|class| !self.0.contains(*class)) {
+            dom::count_op();
+            class_list.remove_1(class)?;
+        }
+        for class in self.0.iter().filter(|class| !old.0.contains(*class)) {
+            dom::count_op();
+            class_list.add_1(class)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for ClassList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, class) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(class)?;
+        }
+        Ok(())
+    }
+}
+
+impl StyleMap {
+    /// Create an empty StyleMap.
+    pub fn new() -> StyleMap {
+        StyleMap::default()
+    }
+
+    /// Sets the value of the style property.
+    pub fn set(&mut self, property: impl Into<Cow<'static, str>>, value: impl Into<Cow<'static, str>>) {
+        self.0.insert(property.into(), value.into());
+    }
+
+    /// Gets the value of the style property.
+    pub fn get(&self, property: &str) -> Option<&str> {
+        self.0.get(property).map(|value| value.as_ref())
+    }
+
+    fn patch(&self, old: &StyleMap, el: &Element) -> Result<(), JsValue> {
+        // Both the HTML & SVG elements have a `style` declaration.
+        let style: CssStyleDeclaration = Reflect::get(el, &JsValue::from_str("style"))?.unchecked_into();
+        for property in old.0.keys().filter(|property| !self.0.contains_key(*property)) {
+            dom::count_op();
+            style.remove_property(property)?;
+        }
+        for (property, value) in self.0.iter() {
+            if old.0.get(property) != Some(value) {
+                dom::count_op();
+                style.set_property(property, value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for StyleMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, (property, value)) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}: {};", property, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl<K: Into<Cow<'static, str>>, V: Into<AttributeValue>> FromIterator<(K, V)> for AttributeMap {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> AttributeMap {
+        AttributeMap(
+            iter.into_iter()
+                .map(|(k, v)| (valid_key(k.into()), v.into()))
+                .collect(),
+        )
+    }
+}
+
+/// Checks the key of an attribute in the map, as the keys decided at runtime
+/// are written into the markup & onto the DOM as they are.
+fn valid_key(key: Cow<'static, str>) -> Cow<'static, str> {
+    if !safety::is_valid_attribute_name(&key) {
+        panic!("`{}` is not a valid attribute name.", key);
+    }
+    key
+}
+
 impl<RCTX: Render> EventListener<RCTX> {
     /// Create a EventListener.
     pub fn new(type_: &'static str, listener: Box<dyn Fn(&RCTX, Event)>) -> EventListener<RCTX> {
@@ -165,11 +372,22 @@ impl Display for Attributes {
         for (k, v) in self.0.iter() {
             match v {
                 AttributeValue::String(ref v) => {
-                    write!(f, " {}=\"{}\"", k, v);
+                    write!(
+                        f,
+                        " {}=\"{}\"",
+                        k,
+                        EscapedAttribute(apply_url_policy(k, v))
+                    )?;
                 }
                 AttributeValue::Bool(truthy) => if *truthy {
                     write!(f, " {}=\"\"", k)?;
                 },
+                AttributeValue::Classes(ref classes) => {
+                    write!(f, " {}=\"{}\"", k, EscapedAttribute(&classes.to_string()))?;
+                }
+                AttributeValue::Styles(ref styles) => {
+                    write!(f, " {}=\"{}\"", k, EscapedAttribute(&styles.to_string()))?;
+                }
             }
         }
         Ok(())
@@ -200,8 +418,16 @@ impl<RCTX: Render> VElement<RCTX> {
             .patch(None, &el, None, render_ctx.clone(), rx_sender.clone())?;
         self.child
             .patch(None, el.as_ref(), None, render_ctx, rx_sender)?;
+        if let Some(ref transition) = self.transition {
+            transition.before_enter(&el)?;
+        }
+        dom::count_op();
         parent.insert_before(el.as_ref(), next)?;
+        // Kept right away, so that a failed patch can remove it.
         self.node = Some(el);
+        if let Some(ref transition) = self.transition {
+            transition.enter(self.node.as_ref().unwrap())?;
+        }
         Ok(())
     }
 }
@@ -238,7 +464,10 @@ impl<RCTX: Render> DOMPatch for VElement<RCTX> {
                 let old_el = old
                     .node
                     .as_ref()
-                    .expect("The old node is expected to be attached to the DOM");
+                    .expect("The old node is expected to be attached to the DOM")
+                    .clone();
+                // Kept right away, so that a failed patch can remove it.
+                self.node = Some(old_el.clone());
                 self.attributes.patch(
                     Some(&mut old.attributes),
                     &old_el,
@@ -260,8 +489,6 @@ impl<RCTX: Render> DOMPatch for VElement<RCTX> {
                     render_ctx.clone(),
                     rx_sender,
                 )?;
-
-                self.node = Some(old_el.clone());
                 Ok(())
             } else {
                 old.remove(parent)?;
@@ -274,18 +501,33 @@ impl<RCTX: Render> DOMPatch for VElement<RCTX> {
 
     fn reorder(&self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
         let el = self.node.as_ref().unwrap();
+        dom::count_op();
         parent.insert_before(el.as_ref(), next)?;
         Ok(())
     }
 
     fn remove(&self, parent: &Node) -> Result<(), JsValue> {
-        let el = self
-            .node
+        let el = match self.node {
+            Some(ref el) if dom::is_child(el.as_ref(), parent) => el,
+            _ => return Ok(()),
+        };
+        // The elements within a removed one leave along with it.
+        let leaving = self
+            .transition
             .as_ref()
-            .expect("The old node is expected to be attached to the DOM");
-        self.child.remove(el.as_ref())?;
-        self.attributes.remove(&el)?;
-        parent.remove_child(el.as_ref())?;
+            .filter(|_| !transition::is_removing());
+        if let Some(transition) = leaving {
+            // A copy leaves in its place, while the element is taken out at once.
+            transition.leave(el, parent)?;
+        }
+        transition::removing(|| {
+            self.child.remove(el.as_ref())?;
+            self.attributes.remove(&el)
+        })?;
+        if leaving.is_none() {
+            dom::count_op();
+            parent.remove_child(el.as_ref())?;
+        }
         Ok(())
     }
 
@@ -319,22 +561,39 @@ impl DOMPatch for Attributes {
         debug_assert!(next.is_none());
         for (k, v) in self.0.iter() {
             // Remove the key from old as it exists in the newer.
-            let existed = if let Some(ref mut old) = old {
-                old.0.swap_remove(k).is_some()
-            } else {
-                false
-            };
-            match v {
-                AttributeValue::String(val) => {
-                    parent.set_attribute(&k, &val)?;
+            let old_v = old.as_mut().and_then(|old| old.0.swap_remove(k));
+            match (v, old_v) {
+                (AttributeValue::Classes(classes), Some(AttributeValue::Classes(ref old_classes)))
+                    if k == "class" =>
+                {
+                    classes.patch(old_classes, parent)?;
+                }
+                (AttributeValue::Styles(styles), Some(AttributeValue::Styles(ref old_styles)))
+                    if k == "style" =>
+                {
+                    styles.patch(old_styles, parent)?;
                 }
-                AttributeValue::Bool(truthy) => {
+                (AttributeValue::String(val), _) => {
+                    dom::count_op();
+                    parent.set_attribute(k, apply_url_policy(k, val))?;
+                }
+                (AttributeValue::Bool(truthy), old_v) => {
                     if *truthy {
-                        parent.set_attribute(&k, "")?;
-                    } else if existed {
-                        parent.remove_attribute(&k)?;
+                        dom::count_op();
+                        parent.set_attribute(k, "")?;
+                    } else if old_v.is_some() {
+                        dom::count_op();
+                        parent.remove_attribute(k)?;
                     }
                 }
+                (AttributeValue::Classes(classes), _) => {
+                    dom::count_op();
+                    parent.set_attribute(k, &classes.to_string())?;
+                }
+                (AttributeValue::Styles(styles), _) => {
+                    dom::count_op();
+                    parent.set_attribute(k, &styles.to_string())?;
+                }
             }
         }
         // Remove the remaining keys.
@@ -350,6 +609,7 @@ impl DOMPatch for Attributes {
 
     fn remove(&self, parent: &Element) -> Result<(), JsValue> {
         for (k, _) in self.0.iter() {
+            dom::count_op();
             parent.remove_attribute(&k)?;
         }
         Ok(())
@@ -380,13 +640,13 @@ impl<RCTX: Render> DOMPatch for EventListeners<RCTX> {
         parent: &Element,
         _: Option<&Element>,
         render_ctx: Shared<Self::RenderContext>,
-        _: MessageSender,
+        rx_sender: MessageSender,
     ) -> Result<(), JsValue> {
         if let Some(old) = old {
             old.remove(parent)?;
         }
         for listener in self.0.iter_mut() {
-            listener.start_listening(parent.as_ref(), render_ctx.clone())?;
+            listener.start_listening(parent.as_ref(), render_ctx.clone(), rx_sender.clone())?;
         }
         Ok(())
     }
@@ -414,6 +674,7 @@ trait EventManager {
         &mut self,
         parent: &EventTarget,
         render_ctx: Shared<Self::RenderContext>,
+        rx_sender: MessageSender,
     ) -> Result<(), JsValue>;
 
     fn stop_listening(&self, parent: &EventTarget) -> Result<(), JsValue>;
@@ -426,11 +687,14 @@ impl<RCTX: Render> EventManager for EventListener<RCTX> {
         &mut self,
         parent: &EventTarget,
         render_ctx: Shared<Self::RenderContext>,
+        rx_sender: MessageSender,
     ) -> Result<(), JsValue> {
         let listener = self.listener.take().unwrap();
+        // The event is handled on behalf of the App.
         let js_closure: Closure<dyn Fn(Event)> = Closure::wrap(Box::new(move |event| {
-            listener(&*render_ctx.borrow(), event)
+            rx_sender.enter(|| listener(&*render_ctx.borrow(), event))
         }));
+        dom::count_op();
         parent
             .add_event_listener_with_callback(&self.type_, js_closure.as_ref().unchecked_ref())?;
         self.dom_listener = Some(js_closure);
@@ -439,6 +703,7 @@ impl<RCTX: Render> EventManager for EventListener<RCTX> {
 
     fn stop_listening(&self, parent: &EventTarget) -> Result<(), JsValue> {
         if let Some(ref dom_listener) = self.dom_listener {
+            dom::count_op();
             parent.remove_event_listener_with_callback(
                 &self.type_,
                 dom_listener.as_ref().unchecked_ref(),
@@ -466,6 +731,18 @@ impl From<String> for AttributeValue {
     }
 }
 
+impl From<ClassList> for AttributeValue {
+    fn from(val: ClassList) -> AttributeValue {
+        AttributeValue::Classes(val)
+    }
+}
+
+impl From<StyleMap> for AttributeValue {
+    fn from(val: StyleMap) -> AttributeValue {
+        AttributeValue::Styles(val)
+    }
+}
+
 impl<'a> From<Cow<'a, str>> for AttributeValue {
     fn from(val: Cow<'a, str>) -> AttributeValue {
         AttributeValue::String(val.into())
@@ -484,7 +761,11 @@ pub mod test {
     use super::*;
     use crate::{
         component::root_render_ctx,
-        vdom::{test::container, vtext::VText},
+        vdom::{
+            safety::{set_url_policy, UrlPolicy},
+            test::container,
+            vtext::VText,
+        },
     };
     use wasm_bindgen_test::*;
 
@@ -517,6 +798,125 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn should_display_spread_attributes() {
+        let extra: AttributeMap = vec![("aria-label", "Close"), ("data-id", "1")]
+            .into_iter()
+            .collect();
+        let mut attributes = extra.to_attributes();
+        attributes.push(Attribute::new(String::from("data-id"), "2"));
+        let button = VElement::<()>::childless("button", attributes, vec![]);
+        assert_eq!(
+            format!("{}", button),
+            "<button aria-label=\"Close\" data-id=\"2\"></button>"
+        );
+    }
+
+    #[test]
+    fn should_display_neutralized_spread_url() {
+        let extra: AttributeMap = vec![("HREF", "javascript:alert(1)")].into_iter().collect();
+        let a = VElement::<()>::childless("a", extra.to_attributes(), vec![]);
+        set_url_policy(UrlPolicy::Neutralize);
+        let displayed = format!("{}", a);
+        set_url_policy(UrlPolicy::Allow);
+
+        assert_eq!(displayed, r#"<a HREF="about:blank#blocked"></a>"#);
+    }
+
+    #[test]
+    #[should_panic(expected = "`onclick=alert(1)` is not a valid attribute name.")]
+    fn should_not_spread_invalid_attribute_names() {
+        let mut extra = AttributeMap::new();
+        extra.insert(String::from("onclick=alert(1)"), "");
+    }
+
+    #[test]
+    fn should_display_escaped_attributes() {
+        let p = VElement::<()>::childless(
+            "p",
+            vec![Attribute::new("title", r#""><script>alert("&")</script>"#)],
+            vec![],
+        );
+        assert_eq!(
+            format!("{}", p),
+            "<p title=\"&quot;&gt;&lt;script&gt;alert(&quot;&amp;&quot;)&lt;/script&gt;\"></p>"
+        );
+    }
+
+    #[test]
+    fn should_display_neutralized_javascript_url() {
+        let a = VElement::<()>::childless(
+            "a",
+            vec![Attribute::new("href", "javascript:alert(1)")],
+            vec![],
+        );
+        set_url_policy(UrlPolicy::Neutralize);
+        let displayed = format!("{}", a);
+        set_url_policy(UrlPolicy::Allow);
+
+        assert_eq!(displayed, r#"<a href="about:blank#blocked"></a>"#);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_neutralized_javascript_url() {
+        let mut a = VElement::childless(
+            "a",
+            vec![Attribute::new("href", "javascript:alert(1)")],
+            vec![],
+        );
+        let div = container();
+        set_url_policy(UrlPolicy::Neutralize);
+        let patched = a.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        );
+        set_url_policy(UrlPolicy::Allow);
+        patched.expect("To patch div");
+
+        assert_eq!(div.inner_html(), r#"<a href="about:blank#blocked"></a>"#);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_spread_attributes() {
+        let extra: AttributeMap = vec![("aria-label", "Home"), ("HREF", "javascript:alert(1)")]
+            .into_iter()
+            .collect();
+        let mut a = VElement::childless("a", extra.to_attributes(), vec![]);
+        let div = container();
+        set_url_policy(UrlPolicy::Neutralize);
+        let patched = a.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        );
+        set_url_policy(UrlPolicy::Allow);
+        patched.expect("To patch div");
+
+        assert_eq!(
+            div.inner_html(),
+            r#"<a aria-label="Home" href="about:blank#blocked"></a>"#
+        );
+    }
+
+    #[wasm_bindgen_test]
+    #[should_panic(expected = "`data id` is not a valid attribute name.")]
+    fn should_not_patch_invalid_spread_attributes() {
+        let extra: AttributeMap = vec![("data id", "1")].into_iter().collect();
+        let mut a = VElement::childless("a", extra.to_attributes(), vec![]);
+        a.patch(
+            None,
+            container().as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        ).expect("To patch div");
+    }
+
     #[wasm_bindgen_test]
     fn should_patch_container_with_button_element() {
         let mut button_el = VElement::childless("button", vec![], vec![]);
@@ -653,4 +1053,148 @@ pub mod test {
             r#"<div class="bg-white txt-black" id="main"></div>"#
         )
     }
+
+    fn class_list(classes: &[&'static str]) -> ClassList {
+        let mut list = ClassList::new();
+        for class in classes {
+            list.add(*class);
+        }
+        list
+    }
+
+    #[test]
+    fn should_display_classes_and_styles() {
+        let mut styles = StyleMap::new();
+        styles.set("width", "100px");
+        styles.set("background-color", "red");
+        let div = VElement::<()>::childless(
+            "div",
+            vec![
+                Attribute::new("class", class_list(&["btn active", "btn"])),
+                Attribute::new("style", styles),
+            ],
+            vec![],
+        );
+        assert_eq!(
+            format!("{}", div),
+            r#"<div class="btn active" style="width: 100px; background-color: red;"></div>"#
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_diff_of_classes() {
+        let mut div_el = VElement::childless(
+            "div",
+            vec![Attribute::new("class", class_list(&["btn", "active"]))],
+            vec![],
+        );
+        let div = container();
+        div_el
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            ).expect("To patch div");
+
+        // A class added outside of the VDOM is kept by the diff.
+        div_el
+            .node
+            .as_ref()
+            .unwrap()
+            .class_list()
+            .add_1("focused")
+            .unwrap();
+
+        let mut div_diff = VElement::childless(
+            "div",
+            vec![Attribute::new("class", class_list(&["btn", "large"]))],
+            vec![],
+        );
+        div_diff
+            .patch(
+                Some(&mut div_el),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            ).expect("To patch div");
+
+        assert_eq!(div.inner_html(), r#"<div class="btn focused large"></div>"#);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_diff_of_styles() {
+        let mut styles = StyleMap::new();
+        styles.set("width", "100px");
+        styles.set("color", "red");
+        let mut div_el = VElement::childless("div", vec![Attribute::new("style", styles)], vec![]);
+        let div = container();
+        div_el
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            ).expect("To patch div");
+
+        let mut styles = StyleMap::new();
+        styles.set("width", "200px");
+        let mut div_diff =
+            VElement::childless("div", vec![Attribute::new("style", styles)], vec![]);
+        div_diff
+            .patch(
+                Some(&mut div_el),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            ).expect("To patch div");
+
+        assert_eq!(div.inner_html(), r#"<div style="width: 200px;"></div>"#);
+    }
+
+    #[wasm_bindgen_test]
+    fn should_delay_removal_of_transitioned_element() {
+        let fade = Transition::new("fade");
+        let faded = VNode::from(VElement::new(
+            "div",
+            vec![],
+            vec![],
+            fade.wrap(VNode::from(VElement::new(
+                "p",
+                vec![],
+                vec![],
+                VNode::from(VText::text("Saved")),
+            ))),
+        ));
+        let mut faded = fade.wrap(faded);
+        let div = container();
+        faded
+            .patch(
+                None,
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            ).expect("To patch div");
+
+        let mut removed = VNode::None;
+        removed
+            .patch(
+                Some(&mut faded),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            ).expect("To patch div");
+
+        // Only the outer element leaves, the inner one along with it.
+        assert_eq!(
+            div.inner_html(),
+            r#"<div class="fade-enter fade-enter-active fade-leave fade-leave-active"><p class="fade-enter fade-enter-active">Saved</p></div>"#
+        );
+    }
 }
@@ -42,6 +42,19 @@ impl<RCTX: Render> From<IndexMap<Key, VNode<RCTX>, FnvBuildHasher>> for VList<RC
     }
 }
 
+impl<RCTX: Render> VList<RCTX> {
+    /// The vnodes along with their keys, to be modified.
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&Key, &mut VNode<RCTX>)> {
+        self.0.iter_mut()
+    }
+
+    /// Iterates over the keyed vnodes in the list.
+    #[cfg(feature = "devtools")]
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&Key, &VNode<RCTX>)> {
+        self.0.iter()
+    }
+}
+
 impl<RCTX: Render> Display for VList<RCTX> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         for (_, vnode) in self.0.iter() {
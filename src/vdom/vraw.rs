@@ -0,0 +1,221 @@
+//! Representation of trusted raw HTML in virtual dom tree.
+
+use crate::{component::Render, dom::{self, DOMPatch}, vdom::VNode, MessageSender, Shared};
+use std::{
+    fmt::{self, Display, Formatter},
+    marker::PhantomData,
+};
+use wasm_bindgen::prelude::JsValue;
+use web_sys::{window, Element, Node};
+
+/// The representation of raw HTML in virtual dom tree.
+///
+/// The HTML content is set as the `innerHTML` of a container element and is
+/// never escaped. Only use it with markup you trust, like the one sanitized by
+/// your CMS or generated by a syntax highlighter.
+pub struct VRaw<RCTX: Render> {
+    /// The tag of the container element. Eg: div, span, ...
+    tag: &'static str,
+    /// The raw HTML content
+    content: String,
+    /// Container reference to the DOM
+    node: Option<Element>,
+    /// Render context
+    _phantom: PhantomData<RCTX>,
+}
+
+impl<RCTX: Render> VRaw<RCTX> {
+    /// Create a VRaw which renders the raw HTML `content` inside a `div`.
+    ///
+    /// The content is not escaped, so never pass any untrusted input to it.
+    pub fn dangerously_new(content: impl Into<String>) -> VRaw<RCTX> {
+        VRaw::dangerously_with_tag("div", content)
+    }
+
+    /// Create a VRaw which renders the raw HTML `content` inside a container
+    /// with the given `tag`.
+    ///
+    /// The content is not escaped, so never pass any untrusted input to it.
+    pub fn dangerously_with_tag(tag: &'static str, content: impl Into<String>) -> VRaw<RCTX> {
+        VRaw {
+            tag,
+            content: content.into(),
+            node: None,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<RCTX: Render> From<VRaw<RCTX>> for VNode<RCTX> {
+    fn from(raw: VRaw<RCTX>) -> VNode<RCTX> {
+        VNode::Raw(raw)
+    }
+}
+
+impl<RCTX: Render> Display for VRaw<RCTX> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "<{tag}>{content}</{tag}>",
+            tag = self.tag,
+            content = self.content
+        )
+    }
+}
+
+impl<RCTX: Render> VRaw<RCTX> {
+    fn patch_new(&mut self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
+        let el = window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .create_element(self.tag)?;
+        dom::count_op();
+        el.set_inner_html(&self.content);
+        dom::count_op();
+        parent.insert_before(el.as_ref(), next)?;
+        self.node = Some(el);
+        Ok(())
+    }
+}
+
+impl<RCTX: Render> DOMPatch for VRaw<RCTX> {
+    type RenderContext = RCTX;
+    type Node = Node;
+
+    fn render_walk(
+        &mut self,
+        _: &Node,
+        _: Option<&Node>,
+        _: Shared<Self::RenderContext>,
+        _: MessageSender,
+    ) -> Result<(), JsValue> {
+        unreachable!("There is nothing to render in a VRaw");
+    }
+
+    fn patch(
+        &mut self,
+        old: Option<&mut Self>,
+        parent: &Node,
+        next: Option<&Node>,
+        _: Shared<Self::RenderContext>,
+        _: MessageSender,
+    ) -> Result<(), JsValue> {
+        if let Some(old) = old {
+            if self.tag == old.tag {
+                let old_el = old
+                    .node
+                    .as_ref()
+                    .expect("The old node is expected to be attached to the DOM");
+                if self.content != old.content {
+                    dom::count_op();
+                    old_el.set_inner_html(&self.content);
+                }
+                self.node = Some(old_el.clone());
+                Ok(())
+            } else {
+                old.remove(parent)?;
+                self.patch_new(parent, next)
+            }
+        } else {
+            self.patch_new(parent, next)
+        }
+    }
+
+    fn reorder(&self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
+        let el = self.node.as_ref().unwrap();
+        dom::count_op();
+        parent.insert_before(el.as_ref(), next)?;
+        Ok(())
+    }
+
+    fn remove(&self, parent: &Node) -> Result<(), JsValue> {
+        let el = match self.node {
+            Some(ref el) if dom::is_child(el.as_ref(), parent) => el,
+            _ => return Ok(()),
+        };
+        dom::count_op();
+        parent.remove_child(el.as_ref())?;
+        Ok(())
+    }
+
+    fn node(&self) -> Option<&Node> {
+        self.node.as_ref().map(|el| el.as_ref())
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::*;
+    use crate::{component::root_render_ctx, vdom::test::container};
+    use wasm_bindgen_test::*;
+
+    #[test]
+    fn should_display_raw_html_unescaped() {
+        let raw = VRaw::<()>::dangerously_new("<em>Hello</em> & <b>World</b>");
+        assert_eq!(format!("{}", raw), "<div><em>Hello</em> & <b>World</b></div>");
+    }
+
+    #[test]
+    fn should_display_raw_html_with_container_tag() {
+        let raw = VRaw::<()>::dangerously_with_tag("span", "<em>Hello</em>");
+        assert_eq!(format!("{}", raw), "<span><em>Hello</em></span>");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_raw_html() {
+        let mut raw = VRaw::dangerously_new("<em>Hello</em>");
+        let div = container();
+        raw.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        ).expect("To patch div");
+
+        assert_eq!(div.inner_html(), "<div><em>Hello</em></div>");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_patch_container_with_raw_html_update() {
+        let mut raw = VRaw::dangerously_new("<em>Hello</em>");
+        let div = container();
+        raw.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        ).expect("To patch div");
+
+        let mut updated = VRaw::dangerously_new("<b>World</b>");
+        updated
+            .patch(
+                Some(&mut raw),
+                div.as_ref(),
+                None,
+                root_render_ctx(),
+                crate::message_sender(),
+            ).expect("To patch div");
+
+        assert_eq!(div.inner_html(), "<div><b>World</b></div>");
+    }
+
+    #[wasm_bindgen_test]
+    fn should_remove_raw_html() {
+        let mut raw = VRaw::dangerously_new("<em>Hello</em>");
+        let div = container();
+        raw.patch(
+            None,
+            div.as_ref(),
+            None,
+            root_render_ctx(),
+            crate::message_sender(),
+        ).expect("To patch div");
+
+        raw.remove(div.as_ref()).expect("To remove from div");
+
+        assert_eq!(div.inner_html(), "");
+    }
+}
@@ -1,6 +1,11 @@
 //! Representation of text/comment in virtual dom tree.
 
-use crate::{component::Render, dom::DOMPatch, vdom::VNode, MessageSender, Shared};
+use crate::{
+    component::Render,
+    dom::{self, DOMPatch},
+    vdom::{safety::EscapedText, VNode},
+    MessageSender, Shared,
+};
 use std::{
     fmt::{self, Display, Formatter},
     marker::PhantomData,
@@ -51,9 +56,9 @@ impl<RCTX: Render> From<VText<RCTX>> for VNode<RCTX> {
 impl<RCTX: Render> Display for VText<RCTX> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         if self.is_comment {
-            write!(f, "<!--{}-->", self.content)
+            write!(f, "<!--{}-->", EscapedText(&self.content))
         } else {
-            write!(f, "{}", self.content)
+            write!(f, "{}", EscapedText(&self.content))
         }
     }
 }
@@ -75,6 +80,7 @@ impl<RCTX: Render> VText<RCTX> {
                 .create_text_node(&self.content)
                 .into()
         };
+        dom::count_op();
         parent.insert_before(&node, next)?;
         self.node = Some(node);
         Ok(())
@@ -110,6 +116,7 @@ impl<RCTX: Render> DOMPatch for VText<RCTX> {
                     .as_ref()
                     .expect("The old node is expected to be attached to the DOM");
                 if self.content != old.content {
+                    dom::count_op();
                     old_node.set_text_content(Some(&self.content));
                 }
                 self.node = Some(old_node.clone());
@@ -125,16 +132,18 @@ impl<RCTX: Render> DOMPatch for VText<RCTX> {
 
     fn reorder(&self, parent: &Node, next: Option<&Node>) -> Result<(), JsValue> {
         let node = self.node.as_ref().unwrap();
+        dom::count_op();
         parent.insert_before(node, next)?;
         Ok(())
     }
 
     fn remove(&self, parent: &Node) -> Result<(), JsValue> {
-        parent.remove_child(
-            self.node
-                .as_ref()
-                .expect("The old node is expected to be attached to the DOM"),
-        )?;
+        let node = match self.node {
+            Some(ref node) if dom::is_child(node, parent) => node,
+            _ => return Ok(()),
+        };
+        dom::count_op();
+        parent.remove_child(node)?;
         Ok(())
     }
 
@@ -164,6 +173,24 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn should_display_escaped_text() {
+        let text = VText::<()>::text("<script>alert(\"Hacked & done!\")</script>");
+        assert_eq!(
+            format!("{}", text),
+            "&lt;script&gt;alert(\"Hacked &amp; done!\")&lt;/script&gt;"
+        );
+    }
+
+    #[test]
+    fn should_display_escaped_comment() {
+        let comment = VText::<()>::comment("--><script>alert(1)</script><!--");
+        assert_eq!(
+            format!("{}", comment),
+            "<!----&gt;&lt;script&gt;alert(1)&lt;/script&gt;&lt;!---->"
+        );
+    }
+
     #[wasm_bindgen_test]
     fn should_patch_container_with_new_text() {
         let mut vtext = VText::text("Hello World! It is nice to render.");
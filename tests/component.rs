@@ -1,6 +1,47 @@
 #![feature(proc_macro_hygiene, decl_macro)]
 
 use ruukh::prelude::*;
+use std::cell::Cell;
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+use web_sys::{window, Element, HtmlElement, MessageChannel};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Mounts the app in a new container.
+fn mount<COMP: Render<Props = (), Events = ()>>() -> Element {
+    let container = window()
+        .unwrap()
+        .document()
+        .unwrap()
+        .create_element("div")
+        .unwrap();
+    App::<COMP>::new().mount(container.clone());
+    container
+}
+
+/// Clicks the element matching the `selector` within the container.
+fn click(container: &Element, selector: &str) {
+    container
+        .query_selector(selector)
+        .unwrap()
+        .expect("No element to click")
+        .unchecked_into::<HtmlElement>()
+        .click();
+}
+
+/// Waits till the apps are re-rendered with the changes made till now. The
+/// messages are delivered in the order they are posted, so a message posted
+/// now arrives after the ones asking the apps to re-render.
+async fn rerendered() {
+    let channel = MessageChannel::new().unwrap();
+    let delivered = js_sys::Promise::new(&mut |resolve, _| {
+        channel.port2().set_onmessage(Some(&resolve));
+    });
+    channel.port1().post_message(&JsValue::NULL).unwrap();
+    JsFuture::from(delivered).await.unwrap();
+}
 
 #[test]
 fn should_build_a_component_from_unit_struct() {
@@ -166,3 +207,964 @@ fn should_build_a_component_with_default_option_props() {
     assert_eq!(props.prop_a, Some(false));
     assert_eq!(props.prop_b, Some(3));
 }
+
+#[test]
+fn should_build_a_custom_element_component() {
+    use ruukh::custom_element::FromAttributes;
+
+    #[component]
+    #[custom_element]
+    #[events(
+        fn changed(&self, count: i32);
+
+        #[optional]
+        fn reset(&self) -> bool;
+    )]
+    struct Counter {
+        initial_count: i32,
+        #[prop(default = 1)]
+        step: i32,
+        label: Option<String>,
+        #[state]
+        count: i32,
+    }
+
+    assert_eq!(
+        CounterProps::OBSERVED_ATTRIBUTES,
+        &["initial-count", "step", "label"]
+    );
+
+    let props = CounterProps::from_attributes(&|name| match name {
+        "initial-count" => Some("5".to_string()),
+        _ => None,
+    }).unwrap()
+    .unwrap();
+    assert_eq!(props.initial_count, 5);
+    assert_eq!(props.step, 1);
+    assert!(props.label.is_none());
+
+    assert!(CounterProps::from_attributes(&|_| None).unwrap().is_none());
+
+    let invalid = CounterProps::from_attributes(&|name| match name {
+        "initial-count" => Some("5".to_string()),
+        "step" => Some("two".to_string()),
+        _ => None,
+    });
+    assert_eq!(invalid.err().unwrap().name, "step");
+}
+
+#[test]
+fn should_expand_component_props_with_literals_and_shorthands() {
+    #[component]
+    struct Label {
+        count: i32,
+        #[prop(default)]
+        hidden: bool,
+        text: &'static str,
+    }
+
+    impl Lifecycle for Label {}
+
+    impl Render for Label {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <span>{ self.text }</span>
+            }
+        }
+    }
+
+    let count = 3;
+    let _: Markup<()> = html! {
+        <Label text="Items" {count} hidden></Label>
+    };
+}
+
+#[test]
+fn should_expand_component_props_with_spread() {
+    #[component]
+    struct Label {
+        count: i32,
+        text: String,
+    }
+
+    impl Lifecycle for Label {}
+
+    impl Render for Label {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <span>{ &self.text }</span>
+            }
+        }
+    }
+
+    fn label_props() -> LabelProps {
+        LabelProps!(count: 1, text: "Items".to_string())
+    }
+
+    // The spread props are borrowed, so they may be spread again.
+    let props = label_props();
+    let _: Markup<()> = html! {
+        <Label ..props count={2}></Label>
+        <Label ..props></Label>
+        <Label ..label_props() count={3}></Label>
+        <Label ..{ LabelProps!(count: 1, text: "Items".to_string()) }></Label>
+    };
+}
+
+#[wasm_bindgen_test]
+async fn should_rerender_only_when_compared_fields_change() {
+    use std::rc::Rc;
+
+    thread_local! {
+        static PAGE_RENDERS: Cell<u32> = const { Cell::new(0) };
+        static LIST_RENDERS: Cell<u32> = const { Cell::new(0) };
+    }
+
+    fn same_len(a: &[i32], b: &[i32]) -> bool {
+        a.len() == b.len()
+    }
+
+    #[derive(Clone, Default)]
+    struct Handle;
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct List {
+        #[prop(compare = "ptr")]
+        items: Rc<Vec<i32>>,
+        #[prop(default, compare = same_len)]
+        selected: Vec<i32>,
+    }
+
+    impl Render for List {
+        fn render(&self) -> Markup<Self> {
+            LIST_RENDERS.with(|renders| renders.set(renders.get() + 1));
+            html! {
+                <p>{ format!("{:?} of {:?}", self.selected, self.items) }</p>
+            }
+        }
+    }
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Page {
+        #[state(default = Rc::new(vec![1, 2, 3]), compare = "ptr")]
+        items: Rc<Vec<i32>>,
+        #[state(default = vec![1])]
+        selected: Vec<i32>,
+        #[state(no_compare)]
+        handle: Handle,
+    }
+
+    impl Render for Page {
+        fn render(&self) -> Markup<Self> {
+            PAGE_RENDERS.with(|renders| renders.set(renders.get() + 1));
+            html! {
+                <List items={self.items.clone()} selected={self.selected.clone()} />
+                <button class="select" @click={|this: &Self, _| this.set_state(|state| state.selected = vec![2])}>"Select"</button>
+                <button class="copy" @click={|this: &Self, _| this.set_state(|state| state.items = Rc::new(state.items.to_vec()))}>"Copy"</button>
+                <button class="touch" @click={|this: &Self, _| this.set_state(|state| state.handle = Handle)}>"Touch"</button>
+            }
+        }
+    }
+
+    let renders = || (PAGE_RENDERS.with(Cell::get), LIST_RENDERS.with(Cell::get));
+    let container = mount::<Page>();
+    assert_eq!(renders(), (1, 1));
+
+    // A selection of the same length is taken to be the same.
+    click(&container, ".select");
+    rerendered().await;
+    assert_eq!(renders(), (2, 1));
+    assert!(container.inner_html().starts_with("<p>[1] of [1, 2, 3]</p>"));
+
+    // Equal items behind another pointer are taken to be changed.
+    click(&container, ".copy");
+    rerendered().await;
+    assert_eq!(renders(), (3, 2));
+    assert!(container.inner_html().starts_with("<p>[2] of [1, 2, 3]</p>"));
+
+    // A field which is not compared always changes.
+    click(&container, ".touch");
+    rerendered().await;
+    assert_eq!(renders(), (4, 2));
+}
+
+#[test]
+fn should_build_a_generic_component() {
+    use std::fmt::Display;
+
+    #[component]
+    #[events(
+        fn select(&self, item: T);
+    )]
+    struct List<T: Clone + Display>
+    where
+        T: PartialEq,
+    {
+        items: Vec<T>,
+        #[state]
+        selected: Option<usize>,
+    }
+
+    impl<T: Clone + Display + PartialEq> Lifecycle for List<T> {}
+
+    impl<T: Clone + Display + PartialEq> Render for List<T> {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <ul>
+                    for item in self.items.iter() {
+                        <li>{ item.to_string() }</li>
+                    }
+                </ul>
+            }
+        }
+    }
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Holder<T: Clone + Default + PartialEq> {
+        #[state]
+        value: T,
+    }
+
+    let props: ListProps<i32> = ListProps!(items: vec![1, 2]);
+    assert_eq!(props.items, vec![1, 2]);
+    assert!(HolderState::<String>::default().value.is_empty());
+
+    let items = vec!["a", "b"];
+    let _: Markup<()> = html! {
+        <List<&'static str> items={items.clone()} @select={|_, _| {}}></List>
+        <List<&str> items={items} @select={|_, _| {}}></List<&str>>
+    };
+}
+
+#[wasm_bindgen_test]
+async fn should_recompute_fields_only_when_deps_change() {
+    thread_local! {
+        static COMPUTES: Cell<u32> = const { Cell::new(0) };
+    }
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct TodoList {
+        #[state]
+        filter: String,
+        #[state(default = vec!["Write docs".to_string(), "Write tests".to_string()])]
+        items: Vec<String>,
+        #[state]
+        clicks: u32,
+        #[computed(deps = [items, filter])]
+        filtered: Vec<String>,
+        #[computed(deps = [filtered])]
+        count: usize,
+        #[computed]
+        title: &'static str,
+    }
+
+    impl TodoList {
+        fn compute_filtered(&self) -> Vec<String> {
+            COMPUTES.with(|computes| computes.set(computes.get() + 1));
+            self.items
+                .iter()
+                .filter(|item| item.contains(self.filter.as_str()))
+                .cloned()
+                .collect()
+        }
+
+        fn compute_count(&self) -> usize {
+            self.filtered.len()
+        }
+
+        fn compute_title(&self) -> &'static str {
+            "Todos"
+        }
+    }
+
+    impl Render for TodoList {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <h1>{ self.title }</h1>
+                <span>{ self.count }</span>
+                <ul>
+                    for item in self.filtered.iter() {
+                        <li>{ item }</li>
+                    }
+                </ul>
+                <button class="click" @click={|this: &Self, _| this.set_state(|state| state.clicks += 1)}>"Click"</button>
+                <button class="filter" @click={|this: &Self, _| this.set_state(|state| state.filter = "docs".to_string())}>"Filter"</button>
+            }
+        }
+    }
+
+    let container = mount::<TodoList>();
+    assert_eq!(COMPUTES.with(Cell::get), 1);
+    assert!(container
+        .inner_html()
+        .starts_with("<h1>Todos</h1><span>2</span>"));
+
+    // A change to a field it does not depend upon keeps the computed value.
+    click(&container, ".click");
+    rerendered().await;
+    assert_eq!(COMPUTES.with(Cell::get), 1);
+
+    click(&container, ".filter");
+    rerendered().await;
+    assert_eq!(COMPUTES.with(Cell::get), 2);
+    assert!(container
+        .inner_html()
+        .starts_with("<h1>Todos</h1><span>1</span><ul><li>Write docs</li></ul>"));
+}
+
+#[wasm_bindgen_test]
+async fn should_invoke_watchers_of_changed_fields() {
+    use std::cell::RefCell;
+
+    thread_local! {
+        static LOG: RefCell<Vec<String>> = const { RefCell::new(vec![]) };
+    }
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Results {
+        query: String,
+        #[state(default = 3)]
+        page: u32,
+    }
+
+    impl Results {
+        #[watch(query)]
+        fn reset_page(&self, old: &String, new: &String) {
+            LOG.with(|log| log.borrow_mut().push(format!("query {:?} -> {:?}", old, new)));
+            self.set_state(|state| state.page = 0);
+        }
+
+        #[watch(page)]
+        fn log_page(&self, old: &u32, new: &u32) {
+            LOG.with(|log| log.borrow_mut().push(format!("page {} -> {}", old, new)));
+        }
+    }
+
+    impl Render for Results {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <p>{ format!("{} at {}", self.query, self.page) }</p>
+            }
+        }
+    }
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Search {
+        #[state]
+        query: String,
+    }
+
+    impl Render for Search {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <Results query={self.query.clone()} />
+                <button @click={|this: &Self, _| this.set_state(|state| state.query = "rust".to_string())}>"Search"</button>
+            }
+        }
+    }
+
+    let container = mount::<Search>();
+    assert!(container.inner_html().starts_with("<p> at 3</p>"));
+    assert!(LOG.with(|log| log.borrow().is_empty()));
+
+    click(&container, "button");
+    rerendered().await;
+    assert!(container.inner_html().starts_with("<p>rust at 0</p>"));
+    assert_eq!(
+        LOG.with(|log| log.borrow().clone()),
+        vec![r#"query "" -> "rust""#, "page 3 -> 0"]
+    );
+}
+
+#[test]
+fn should_build_a_component_with_state_initialized_from_props() {
+    #[component]
+    #[derive(Lifecycle)]
+    struct Editor {
+        #[state(init = props.initial_value.clone())]
+        draft: String,
+        initial_value: String,
+        #[state(init = props.initial_value.len(), default = 1)]
+        length: usize,
+        #[state]
+        touched: bool,
+    }
+
+    let props = EditorProps!(initial_value: "Hello".to_string());
+    let state = Editor::initial_state(&props);
+    assert_eq!(state.draft, "Hello");
+    assert_eq!(state.length, 5);
+    assert!(!state.touched);
+    assert_eq!(EditorState::default().length, 1);
+}
+
+#[cfg(feature = "persist")]
+#[wasm_bindgen_test]
+async fn should_build_a_component_with_persisted_state() {
+    use ruukh::storage::{self, MemoryStorage};
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Sidebar {
+        #[state(persist = "sidebar.collapsed")]
+        collapsed: bool,
+        #[state(default = vec!["Home".to_string()], persist = "sidebar.pinned")]
+        pinned: Vec<String>,
+    }
+
+    impl Render for Sidebar {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <p>{ format!("{} {}", self.collapsed, self.pinned.join(",")) }</p>
+                <button class="toggle" @click={|this: &Self, _| this.set_state(|state| state.collapsed = !state.collapsed)}>"Toggle"</button>
+            }
+        }
+    }
+
+    storage::set_storage(MemoryStorage::new());
+    storage::save("sidebar.collapsed", &true);
+
+    // The stored value is loaded, while the one not stored is the default.
+    let container = mount::<Sidebar>();
+    let text = || container.query_selector("p").unwrap().unwrap().text_content().unwrap();
+    assert_eq!(text(), "true Home");
+
+    click(&container, ".toggle");
+    rerendered().await;
+    assert_eq!(text(), "false Home");
+    assert_eq!(storage::load::<bool>("sidebar.collapsed"), Some(false));
+    assert_eq!(storage::load::<Vec<String>>("sidebar.pinned"), None);
+}
+
+#[wasm_bindgen_test]
+async fn should_undo_and_redo_state_changes() {
+    use web_sys::Event;
+
+    #[component(history = 20)]
+    #[derive(Lifecycle)]
+    struct Editor {
+        #[state]
+        text: String,
+    }
+
+    impl Render for Editor {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <p>{ format!("{} {} {}", self.text, self.can_undo(), self.can_redo()) }</p>
+                <button class="type" @click={Self::type_text}>"Type"</button>
+                <button class="keep" @click={Self::keep_text}>"Keep"</button>
+                <button class="clear" @click={Self::clear_text}>"Clear"</button>
+                <button class="undo" @click={Self::undo_text}>"Undo"</button>
+                <button class="redo" @click={Self::redo_text}>"Redo"</button>
+            }
+        }
+    }
+
+    impl Editor {
+        fn type_text(&self, _: Event) {
+            self.set_state(|state| state.text.push('a'));
+        }
+
+        fn keep_text(&self, _: Event) {
+            self.set_state(|state| state.text = state.text.clone());
+        }
+
+        fn clear_text(&self, _: Event) {
+            self.transaction(|| {
+                self.set_state(|state| state.text.clear());
+                self.set_state(|state| state.text.push('-'));
+            });
+        }
+
+        fn undo_text(&self, _: Event) {
+            self.undo();
+        }
+
+        fn redo_text(&self, _: Event) {
+            self.redo();
+        }
+    }
+
+    let container = mount::<Editor>();
+    let text = || {
+        container
+            .query_selector("p")
+            .unwrap()
+            .unwrap()
+            .inner_html()
+    };
+    assert_eq!(text(), " false false");
+
+    click(&container, ".type");
+    click(&container, ".type");
+    // Nothing is recorded when the state does not change.
+    click(&container, ".keep");
+    click(&container, ".clear");
+    rerendered().await;
+    assert_eq!(text(), "- true false");
+
+    // The transaction is undone as a single step.
+    click(&container, ".undo");
+    rerendered().await;
+    assert_eq!(text(), "aa true true");
+
+    click(&container, ".undo");
+    rerendered().await;
+    assert_eq!(text(), "a true true");
+
+    click(&container, ".redo");
+    rerendered().await;
+    assert_eq!(text(), "aa true true");
+}
+
+#[test]
+fn should_build_an_inspectable_component() {
+    #[component(history, inspect)]
+    #[derive(Lifecycle)]
+    struct Todo {
+        #[state]
+        done: bool,
+        title: String,
+    }
+
+    #[component(inspect)]
+    #[derive(Lifecycle)]
+    struct Label {
+        text: String,
+    }
+
+    assert_eq!(TodoProps!(title: "Write docs".to_string()).title, "Write docs");
+    assert_eq!(LabelProps!(text: "Done".to_string()).text, "Done");
+}
+
+#[wasm_bindgen_test]
+async fn should_run_callbacks_after_the_render() {
+    use web_sys::Event;
+
+    thread_local! {
+        static RENDERS: Cell<u32> = const { Cell::new(0) };
+        static RENDERED: Cell<Option<u32>> = const { Cell::new(None) };
+    }
+
+    fn renders() -> u32 {
+        RENDERS.with(Cell::get)
+    }
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Search {
+        #[state]
+        editing: bool,
+        #[state]
+        query: String,
+    }
+
+    impl Render for Search {
+        fn render(&self) -> Markup<Self> {
+            RENDERS.with(|renders| renders.set(renders.get() + 1));
+            html! {
+                <p>{ format!("{} {}", self.query, self.editing) }</p>
+                <button class="edit" @click={Self::edit}>"Edit"</button>
+                <button class="reset" @click={Self::reset}>"Reset"</button>
+                <button class="refresh" @click={Self::refresh}>"Refresh"</button>
+            }
+        }
+    }
+
+    impl Search {
+        fn edit(&self, _: Event) {
+            self.set_state_then(
+                |state| {
+                    state.editing = true;
+                    state.query = "ruukh".to_string();
+                },
+                || RENDERED.with(|rendered| rendered.set(Some(renders()))),
+            );
+        }
+
+        fn reset(&self, _: Event) {
+            ruukh::batch(|| {
+                self.set_state(|state| state.query.clear());
+                self.set_state(|state| state.editing = false);
+            });
+            self.next_tick(|| RENDERED.with(|rendered| rendered.set(Some(renders()))));
+        }
+
+        fn refresh(&self, _: Event) {
+            self.force_update();
+        }
+    }
+
+    let container = mount::<Search>();
+    let text = || {
+        container
+            .query_selector("p")
+            .unwrap()
+            .unwrap()
+            .inner_html()
+    };
+    assert_eq!(renders(), 1);
+
+    // The callback runs once the change is rendered.
+    click(&container, ".edit");
+    assert_eq!(RENDERED.with(Cell::get), None);
+    rerendered().await;
+    assert_eq!(text(), "ruukh true");
+    assert_eq!(RENDERED.with(Cell::get), Some(2));
+
+    // The batched changes are rendered together.
+    click(&container, ".reset");
+    rerendered().await;
+    assert_eq!(text(), " false");
+    assert_eq!(renders(), 3);
+    assert_eq!(RENDERED.with(Cell::get), Some(3));
+
+    // The component is re-rendered even though its state is unchanged.
+    click(&container, ".refresh");
+    rerendered().await;
+    assert_eq!(renders(), 4);
+}
+
+#[wasm_bindgen_test]
+async fn should_run_callbacks_of_a_component_without_state() {
+    thread_local! {
+        static TICKS: Cell<u32> = const { Cell::new(0) };
+    }
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Toolbar;
+
+    impl Render for Toolbar {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <button class="tick" @click={|this: &Self, _| this.next_tick(|| TICKS.with(|ticks| ticks.set(ticks.get() + 1)))}>"Tick"</button>
+            }
+        }
+    }
+
+    // The callback goes through the App the event is handled in.
+    let container = mount::<Toolbar>();
+    click(&container, ".tick");
+    assert_eq!(TICKS.with(Cell::get), 0);
+    rerendered().await;
+    assert_eq!(TICKS.with(Cell::get), 1);
+}
+
+#[test]
+fn should_build_a_function_component() {
+    #[component]
+    fn Badge(#[prop(default)] color: String, label: String) -> Markup<Self> {
+        html! {
+            <span class={color.as_str()}>{ label }</span>
+        }
+    }
+
+    #[component]
+    fn Separator() -> Markup<Self> {
+        html! {
+            <hr>
+        }
+    }
+
+    #[component]
+    fn Tag<T: std::fmt::Display + PartialEq>(value: T) -> Markup<Self> {
+        html! {
+            <code>{ value.to_string() }</code>
+        }
+    }
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Labels;
+
+    impl Render for Labels {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <Badge label={"New".to_string()} />
+                <Separator></Separator>
+                <Badge label={"Old".to_string()} color={"gray".to_string()}></Badge>
+                <Tag<i32> value={5} />
+            }
+        }
+    }
+}
+
+#[test]
+fn should_build_a_function_component_with_hooks() {
+    #[component]
+    fn Counter(#[prop(default)] step: i32) -> Markup<Self> {
+        let (count, set_count) = use_state(|| 0);
+        let clicks = use_ref(|| 0);
+        let label = use_memo(count, || format!("Clicked {} times", count));
+        use_effect(*step, move || {
+            *clicks.borrow_mut() = 0;
+            || {}
+        });
+        let step = *step;
+
+        html! {
+            <button @click={move |_, _| set_count.set(count + step)}>{ label.as_str() }</button>
+        }
+    }
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Counters;
+
+    impl Render for Counters {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <Counter />
+                <Counter step={2} />
+            }
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+async fn should_show_the_fallback_till_the_components_are_loaded() {
+    use ruukh::suspense;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static RESOLVE: RefCell<Option<js_sys::Function>> = const { RefCell::new(None) };
+        static MOUNTS: Cell<u32> = const { Cell::new(0) };
+    }
+
+    #[component]
+    struct Profile {
+        id: u32,
+        #[state]
+        name: String,
+    }
+
+    impl Lifecycle for Profile {
+        fn load(&self) -> Option<Loading> {
+            let fetched = js_sys::Promise::new(&mut |resolve, _| {
+                RESOLVE.with(|stored| *stored.borrow_mut() = Some(resolve));
+            });
+            let setter = self.state_setter();
+            let id = self.id;
+            Some(suspense::load(JsFuture::from(fetched), move |_| {
+                setter.set_state(|state| state.name = format!("User {}", id));
+            }))
+        }
+    }
+
+    impl Render for Profile {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <p>{ &self.name }</p>
+            }
+        }
+    }
+
+    #[component]
+    struct Clock;
+
+    impl Lifecycle for Clock {
+        fn mounted(&self) {
+            MOUNTS.with(|mounts| mounts.set(mounts.get() + 1));
+        }
+    }
+
+    impl Render for Clock {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <span>"Clock"</span>
+            }
+        }
+    }
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Profiles;
+
+    impl Render for Profiles {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <Clock />
+                <Profile id={1} />
+            }
+        }
+
+        fn fallback(&self) -> Option<Markup<Self>> {
+            Some(html! {
+                <p>"Loading..."</p>
+            })
+        }
+    }
+
+    let container = mount::<Profiles>();
+    assert_eq!(container.text_content().unwrap(), "Loading...");
+    assert_eq!(MOUNTS.with(Cell::get), 1);
+
+    let resolve = RESOLVE.with(|stored| stored.borrow_mut().take()).unwrap();
+    resolve.call0(&JsValue::NULL).unwrap();
+    // The load completes before the first message is delivered, so the
+    // second one arrives after the re-render it asks for.
+    rerendered().await;
+    rerendered().await;
+    assert_eq!(container.text_content().unwrap(), "ClockUser 1");
+    assert_eq!(MOUNTS.with(Cell::get), 1);
+}
+
+#[wasm_bindgen_test]
+async fn should_show_the_error_fallback_without_retrying() {
+    use ruukh::vdom::vraw::VRaw;
+    use std::cell::RefCell;
+
+    thread_local! {
+        static CAUGHT: RefCell<Vec<String>> = const { RefCell::new(vec![]) };
+    }
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Chart {
+        points: Vec<i32>,
+    }
+
+    impl Render for Chart {
+        fn render(&self) -> Markup<Self> {
+            match self.points.iter().max() {
+                Some(max) => html! {
+                    <p>{ max.to_string() }</p>
+                },
+                // An invalid tag fails to be created in the DOM.
+                None => html! {
+                    { VRaw::dangerously_with_tag("no points", "") }
+                },
+            }
+        }
+    }
+
+    #[component]
+    struct Dashboard {
+        #[state]
+        failures: u32,
+    }
+
+    impl Lifecycle for Dashboard {
+        fn caught_error(&self, error: CaughtError) {
+            CAUGHT.with(|caught| caught.borrow_mut().push(error.path.join(" > ")));
+            // Re-rendering the boundary does not try the failed render again.
+            self.set_state(|state| state.failures += 1);
+        }
+    }
+
+    impl Render for Dashboard {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <Chart points={vec![]} />
+            }
+        }
+
+        fn error_fallback(&self, _: &CaughtError) -> Option<Markup<Self>> {
+            Some(html! {
+                <p>"The chart could not be shown."</p>
+            })
+        }
+    }
+
+    let container = mount::<Dashboard>();
+    rerendered().await;
+    rerendered().await;
+    assert_eq!(
+        container.text_content().unwrap(),
+        "The chart could not be shown."
+    );
+    CAUGHT.with(|caught| {
+        let caught = caught.borrow();
+        assert_eq!(caught.len(), 1);
+        assert!(caught[0].ends_with("Chart"));
+    });
+}
+
+#[wasm_bindgen_test]
+async fn should_remove_what_a_failed_patch_got_into_the_dom() {
+    use ruukh::vdom::vraw::VRaw;
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Chart;
+
+    impl Render for Chart {
+        fn render(&self) -> Markup<Self> {
+            // The legend is patched in before the invalid tag fails.
+            html! {
+                { VRaw::dangerously_with_tag("no points", "") }
+                <p>"Legend"</p>
+            }
+        }
+    }
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Dashboard;
+
+    impl Render for Dashboard {
+        fn render(&self) -> Markup<Self> {
+            html! {
+                <h1>"Dashboard"</h1>
+                <Chart />
+            }
+        }
+
+        fn error_fallback(&self, _: &CaughtError) -> Option<Markup<Self>> {
+            Some(html! {
+                <p>"The chart could not be shown."</p>
+            })
+        }
+    }
+
+    let container = mount::<Dashboard>();
+    assert_eq!(
+        container.inner_html(),
+        "<p>The chart could not be shown.</p>"
+    );
+
+    #[component]
+    #[derive(Lifecycle)]
+    struct Report {
+        #[state]
+        broken: bool,
+    }
+
+    impl Render for Report {
+        fn render(&self) -> Markup<Self> {
+            if self.broken {
+                html! {
+                    { VRaw::dangerously_with_tag("no summary", "") }
+                    <p>"Summary"</p>
+                }
+            } else {
+                html! {
+                    <p>"Summary"</p>
+                    <button class="break" @click={|this: &Self, _| this.set_state(|state| state.broken = true)}>"Break"</button>
+                }
+            }
+        }
+
+        fn error_fallback(&self, _: &CaughtError) -> Option<Markup<Self>> {
+            Some(html! {
+                <p>"The report could not be shown."</p>
+            })
+        }
+    }
+
+    // The failed patch of the boundary's own render is caught too.
+    let container = mount::<Report>();
+    click(&container, ".break");
+    rerendered().await;
+    assert_eq!(
+        container.inner_html(),
+        "<p>The report could not be shown.</p>"
+    );
+}
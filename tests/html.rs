@@ -62,3 +62,141 @@ fn should_expand_element_with_attributes() {
         </button>
     };
 }
+
+#[test]
+fn should_expand_if_else() {
+    let visible = false;
+    let markup: Markup<()> = html! {
+        if visible {
+            <div>"Visible"</div>
+        } else if let Some(reason) = Some("empty") {
+            "Hidden as "{ reason }
+        } else {
+            "Hidden"
+        }
+    };
+    assert_eq!(markup.to_string(), "Hidden as empty");
+}
+
+#[test]
+fn should_expand_if_without_else() {
+    let markup: Markup<()> = html! {
+        if false {
+            <div>"Visible"</div>
+        }
+    };
+    assert_eq!(markup.to_string(), "");
+}
+
+#[test]
+fn should_expand_for() {
+    let items = ["Hello", "World"];
+    let markup: Markup<()> = html! {
+        <ul>
+            for item in items.iter() {
+                <li>{ *item }</li>
+            }
+        </ul>
+    };
+    assert_eq!(markup.to_string(), "<ul><li>Hello</li><li>World</li></ul>");
+}
+
+#[test]
+fn should_expand_keyed_for() {
+    let items = ["Hello", "World"];
+    let markup: Markup<()> = html! {
+        for (index, item) in items.iter().enumerate() {
+            <li key={index as u32}>{ *item }</li>
+        }
+    };
+    assert_eq!(markup.to_string(), "<li>Hello</li><li>World</li>");
+}
+
+#[test]
+fn should_expand_match() {
+    let markup: Markup<()> = html! {
+        match Some(3) {
+            Some(0) | None => "Nothing",
+            Some(n) if n > 2 => {
+                <span>"Many: "{ n }</span>
+            }
+            Some(n) => <span>{ n }</span>
+        }
+    };
+    assert_eq!(markup.to_string(), "<span>Many: 3</span>");
+}
+
+#[test]
+fn should_expand_element_with_literal_attributes() {
+    let markup: Markup<()> = html! {
+        <button class="btn" name={"submit"}>"Click"</button>
+    };
+    assert_eq!(
+        markup.to_string(),
+        r#"<button class="btn" name="submit">Click</button>"#
+    );
+}
+
+#[test]
+fn should_expand_element_with_valueless_attributes() {
+    let markup: Markup<()> = html! {
+        <input disabled>
+    };
+    assert_eq!(markup.to_string(), r#"<input disabled="">"#);
+}
+
+#[test]
+fn should_expand_element_with_shorthand_attributes() {
+    let class = "btn";
+    let markup: Markup<()> = html! {
+        <button {class}>"Click"</button>
+    };
+    assert_eq!(markup.to_string(), r#"<button class="btn">Click</button>"#);
+}
+
+#[test]
+fn should_expand_element_with_spread_attributes() {
+    use ruukh::vdom::velement::AttributeMap;
+
+    let mut extra = AttributeMap::new();
+    extra.insert(format!("data-{}", "id"), "1");
+    extra.insert("class", "link");
+    let markup: Markup<()> = html! {
+        <a ..extra class="btn">"Click"</a>
+        <input ..extra />
+    };
+    assert_eq!(
+        markup.to_string(),
+        r#"<a data-id="1" class="btn">Click</a><input data-id="1" class="link">"#
+    );
+}
+
+#[test]
+fn should_expand_classes_and_styles() {
+    let active = true;
+    let markup: Markup<()> = html! {
+        <div
+            class={classes!["btn", "active" => active, "hidden" => !active]}
+            style={styles!{ width: "100px", box-sizing: "border-box", "--accent": "red" }}
+        ></div>
+    };
+    assert_eq!(
+        markup.to_string(),
+        r#"<div class="btn active" style="width: 100px; box-sizing: border-box; --accent: red;"></div>"#
+    );
+}
+
+#[test]
+fn should_expand_transition_wrapped_markup() {
+    let saved = true;
+    let markup: Markup<()> = html! {
+        <div>
+            { Transition::new("fade").wrap(html! {
+                if saved {
+                    <p>"Saved"</p>
+                }
+            }) }
+        </div>
+    };
+    assert_eq!(markup.to_string(), "<div><p>Saved</p></div>");
+}